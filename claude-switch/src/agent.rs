@@ -0,0 +1,112 @@
+//! Client side of the unlock agent.
+//!
+//! `claude-switch agent` keeps the store key in memory and answers
+//! newline-delimited JSON requests on a Unix socket. Commands find it
+//! through `CLAUDE_SWITCH_AGENT_SOCK`, the way ssh tools find
+//! `SSH_AUTH_SOCK`.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use crate::error::{Result, SwitchError};
+use crate::store::StoreKey;
+
+/// Environment variable that points commands at a running agent
+pub const SOCKET_ENV: &str = "CLAUDE_SWITCH_AGENT_SOCK";
+
+/// A request sent to the agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "kebab-case")]
+pub enum Request {
+    /// Hand out the key, if unlocked
+    Get,
+    /// Hold this key
+    Set { key: String },
+    /// Forget the key
+    Lock,
+    /// Report whether a key is held
+    Status,
+}
+
+/// The agent's answer to a [`Request`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub ok: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+
+    #[serde(default)]
+    pub unlocked: bool,
+
+    /// Milliseconds until the key is forgotten for lack of use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_remaining_ms: Option<i64>,
+}
+
+impl Response {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            error: Some(message.into()),
+            ..Self::default()
+        }
+    }
+}
+
+/// Socket of the agent named in the environment, if any
+pub fn socket_from_env() -> Option<PathBuf> {
+    std::env::var_os(SOCKET_ENV)
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Ask the agent for the store key. Returns `None` when no agent is
+/// configured, it can't be reached, or it is locked.
+pub fn get_key() -> Option<StoreKey> {
+    let response = request(&Request::Get).ok()?;
+    let hex = zeroize::Zeroizing::new(response.key?);
+    let bytes = zeroize::Zeroizing::new(crate::store::hex_decode(&hex)?);
+    let key: [u8; 32] = bytes.as_slice().try_into().ok()?;
+    Some(zeroize::Zeroizing::new(key))
+}
+
+/// Hand the store key to the agent
+pub fn set_key(key: &StoreKey) -> Result<Response> {
+    let hex = zeroize::Zeroizing::new(crate::store::hex_encode(key.as_ref()));
+    request(&Request::Set { key: hex.to_string() })
+}
+
+/// Send one request to the agent named in the environment
+#[cfg(unix)]
+pub fn request(request: &Request) -> Result<Response> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    let socket = socket_from_env().ok_or(SwitchError::AgentNotRunning)?;
+    let mut stream = UnixStream::connect(&socket).map_err(|_| SwitchError::AgentNotRunning)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+
+    let mut line = zeroize::Zeroizing::new(serde_json::to_string(request)?);
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+
+    let mut reply = zeroize::Zeroizing::new(String::new());
+    BufReader::new(stream).read_line(&mut reply)?;
+    let response: Response = serde_json::from_str(&reply)?;
+
+    match (&response.error, response.ok) {
+        (Some(error), false) => Err(SwitchError::AgentError(error.clone())),
+        _ => Ok(response),
+    }
+}
+
+/// The agent needs Unix sockets
+#[cfg(not(unix))]
+pub fn request(_request: &Request) -> Result<Response> {
+    Err(SwitchError::AgentNotRunning)
+}
@@ -0,0 +1,390 @@
+//! Hash-chained audit log of changes to the account store.
+//!
+//! Each entry carries the hash of the one before it, so editing or
+//! removing an entry breaks the chain. The latest sequence number and hash
+//! are also kept in a separate head file, which catches truncation.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use crate::credentials::{token_fingerprint, Credentials};
+use crate::error::{Result, SwitchError};
+use crate::paths;
+
+/// `prev` of the first entry
+const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// The hashed part of an audit entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditBody {
+    pub seq: u64,
+    pub at: i64,
+    pub action: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub details: BTreeMap<String, String>,
+
+    /// Account name to redacted token fingerprint
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fingerprints: BTreeMap<String, String>,
+
+    pub user: String,
+    pub pid: u32,
+    pub argv: Vec<String>,
+
+    /// Hash of the previous entry
+    pub prev: String,
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    #[serde(flatten)]
+    pub body: AuditBody,
+    pub hash: String,
+}
+
+/// Sequence number and hash of the newest entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AuditHead {
+    seq: u64,
+    hash: String,
+}
+
+/// A store mutation about to be logged
+#[derive(Debug, Clone, Default)]
+pub struct AuditEvent {
+    action: String,
+    account: Option<String>,
+    details: BTreeMap<String, String>,
+    fingerprints: BTreeMap<String, String>,
+}
+
+impl AuditEvent {
+    pub fn new(action: &str, account: Option<&str>) -> Self {
+        Self {
+            action: action.to_string(),
+            account: account.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    pub fn detail(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.details.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Record a fingerprint of an account's access token, never the token
+    pub fn fingerprint(mut self, account: &str, creds: &Credentials) -> Self {
+        self.fingerprints.insert(
+            account.to_string(),
+            token_fingerprint(&creds.claude_ai_oauth.access_token),
+        );
+        self
+    }
+
+    /// Append the event to the audit log
+    pub fn record(self) -> Result<()> {
+        paths::ensure_accounts_dir()?;
+        self.append(&paths::audit_log_path()?, &paths::audit_head_path()?)
+    }
+
+    fn append(self, path: &Path, head_path: &Path) -> Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        // Held until `file` is closed, so concurrent commands (say, the
+        // daemon and a manual switch) can't both extend the same head
+        lock_exclusive(&file)?;
+
+        let (seq, prev) = match read_head(head_path)? {
+            Some(head) => (head.seq + 1, head.hash),
+            None => (0, GENESIS.to_string()),
+        };
+
+        let body = AuditBody {
+            seq,
+            at: chrono::Utc::now().timestamp_millis(),
+            action: self.action,
+            account: self.account,
+            details: self.details,
+            fingerprints: self.fingerprints,
+            user: os_user(),
+            pid: std::process::id(),
+            argv: std::env::args().collect(),
+            prev,
+        };
+        let entry = AuditEntry {
+            hash: hash_body(&body)?,
+            body,
+        };
+
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        restrict_permissions(path)?;
+
+        write_head(
+            head_path,
+            &AuditHead {
+                seq: entry.body.seq,
+                hash: entry.hash,
+            },
+        )
+    }
+}
+
+/// Load every audit entry in file order
+pub fn load() -> Result<Vec<AuditEntry>> {
+    load_from(&paths::audit_log_path()?)
+}
+
+fn load_from(path: &Path) -> Result<Vec<AuditEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file = std::fs::File::open(path)?;
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line?;
+            serde_json::from_str(&line).map_err(|_| {
+                SwitchError::AuditLogTampered(format!("line {} is not a valid audit entry", i + 1))
+            })
+        })
+        .collect()
+}
+
+/// Check the hash chain and the head record, returning the number of
+/// entries if the log is intact
+pub fn verify() -> Result<usize> {
+    verify_files(&paths::audit_log_path()?, &paths::audit_head_path()?)
+}
+
+fn verify_files(path: &Path, head_path: &Path) -> Result<usize> {
+    let entries = load_from(path)?;
+    let tampered = |msg: String| Err(SwitchError::AuditLogTampered(msg));
+
+    let mut prev = GENESIS.to_string();
+    for (i, entry) in entries.iter().enumerate() {
+        let line = i + 1;
+        if entry.body.seq != i as u64 {
+            return tampered(format!(
+                "line {} has sequence number {}, expected {}",
+                line, entry.body.seq, i
+            ));
+        }
+        if entry.body.prev != prev {
+            return tampered(format!("line {} does not follow the entry before it", line));
+        }
+        if hash_body(&entry.body)? != entry.hash {
+            return tampered(format!("line {} was modified", line));
+        }
+        prev = entry.hash.clone();
+    }
+
+    match (read_head(head_path)?, entries.last()) {
+        (None, None) => {}
+        (None, Some(_)) => return tampered("the head record is missing".to_string()),
+        (Some(head), None) => {
+            return tampered(format!("the log is empty but {} entries were written", head.seq + 1))
+        }
+        (Some(head), Some(last)) => {
+            if head.seq > last.body.seq {
+                return tampered(format!(
+                    "the log was truncated: {} entries were written, {} remain",
+                    head.seq + 1,
+                    entries.len()
+                ));
+            }
+            if head.seq != last.body.seq || head.hash != last.hash {
+                return tampered("the head record does not match the last entry".to_string());
+            }
+        }
+    }
+
+    Ok(entries.len())
+}
+
+fn hash_body(body: &AuditBody) -> Result<String> {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest(serde_json::to_string(body)?.as_bytes());
+    Ok(digest.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn read_head(path: &Path) -> Result<Option<AuditHead>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(path)?;
+    serde_json::from_str(&contents)
+        .map(Some)
+        .map_err(|_| SwitchError::AuditLogTampered("the head record is unreadable".to_string()))
+}
+
+fn write_head(path: &Path, head: &AuditHead) -> Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string(head)?)?;
+    restrict_permissions(&tmp)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Block until this process holds an exclusive lock on `file`. The lock
+/// is released when the file is closed.
+#[cfg(unix)]
+pub(crate) fn lock_exclusive(file: &std::fs::File) -> Result<()> {
+    use std::os::unix::io::AsRawFd;
+    // SAFETY: flock only takes a file descriptor that `file` keeps open
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn lock_exclusive(_file: &std::fs::File) -> Result<()> {
+    Ok(())
+}
+
+/// Name of the user running the command, from the real uid rather than
+/// the environment, which is easy to spoof
+#[cfg(unix)]
+fn os_user() -> String {
+    // SAFETY: getpwuid returns null or a pointer into static storage; the
+    // name is copied out before anything else can reuse it
+    unsafe {
+        let uid = libc::getuid();
+        let pw = libc::getpwuid(uid);
+        if pw.is_null() || (*pw).pw_name.is_null() {
+            format!("uid:{}", uid)
+        } else {
+            std::ffi::CStr::from_ptr((*pw).pw_name).to_string_lossy().into_owned()
+        }
+    }
+}
+
+/// Name of the user running the command
+#[cfg(not(unix))]
+fn os_user() -> String {
+    std::env::var("USERNAME").unwrap_or_else(|_| "unknown".to_string())
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Log {
+        _dir: tempfile::TempDir,
+        path: std::path::PathBuf,
+        head: std::path::PathBuf,
+    }
+
+    fn log_with(entries: usize) -> Log {
+        let dir = tempfile::tempdir().unwrap();
+        let log = Log {
+            path: dir.path().join("audit.log"),
+            head: dir.path().join(".audit.head.json"),
+            _dir: dir,
+        };
+        for i in 0..entries {
+            AuditEvent::new("use", Some(&format!("account-{}", i)))
+                .detail("trigger", "manual")
+                .append(&log.path, &log.head)
+                .unwrap();
+        }
+        log
+    }
+
+    fn rewrite_lines(path: &Path, edit: impl FnOnce(&mut Vec<String>)) {
+        let contents = std::fs::read_to_string(path).unwrap();
+        let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+        edit(&mut lines);
+        std::fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    fn tamper_message(log: &Log) -> String {
+        match verify_files(&log.path, &log.head) {
+            Err(SwitchError::AuditLogTampered(msg)) => msg,
+            other => panic!("expected tampering to be detected, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn intact_chain_verifies() {
+        let log = log_with(3);
+        assert_eq!(verify_files(&log.path, &log.head).unwrap(), 3);
+
+        let entries = load_from(&log.path).unwrap();
+        assert_eq!(entries[0].body.prev, GENESIS);
+        assert_eq!(entries[2].body.prev, entries[1].hash);
+    }
+
+    #[test]
+    fn empty_log_verifies() {
+        let log = log_with(0);
+        assert_eq!(verify_files(&log.path, &log.head).unwrap(), 0);
+    }
+
+    #[test]
+    fn edited_entry_is_detected() {
+        let log = log_with(3);
+        rewrite_lines(&log.path, |lines| lines[1] = lines[1].replace("account-1", "account-x"));
+        assert!(tamper_message(&log).contains("line 2 was modified"));
+    }
+
+    #[test]
+    fn removed_entry_is_detected() {
+        let log = log_with(3);
+        rewrite_lines(&log.path, |lines| {
+            lines.remove(1);
+        });
+        assert!(tamper_message(&log).contains("line 2"));
+    }
+
+    #[test]
+    fn truncation_is_detected() {
+        let log = log_with(3);
+        rewrite_lines(&log.path, |lines| lines.truncate(2));
+        assert!(tamper_message(&log).contains("truncated"));
+    }
+
+    #[test]
+    fn missing_head_is_detected() {
+        let log = log_with(2);
+        std::fs::remove_file(&log.head).unwrap();
+        assert!(tamper_message(&log).contains("head record is missing"));
+    }
+
+    #[test]
+    fn concurrent_appends_keep_one_chain() {
+        let log = log_with(0);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..10 {
+                        AuditEvent::new("tag", None).append(&log.path, &log.head).unwrap();
+                    }
+                });
+            }
+        });
+        assert_eq!(verify_files(&log.path, &log.head).unwrap(), 40);
+    }
+}
@@ -1,8 +1,12 @@
 use anyhow::Result;
 use colored::Colorize;
+use crate::audit::AuditEvent;
+use crate::config::Config;
 use crate::credentials::Credentials;
+use crate::hooks::{self, HookEvent};
 use crate::metadata::{AccountInfo, AccountsMetadata, validate_account_name};
 use crate::paths;
+use crate::ui;
 use crate::error::SwitchError;
 
 /// Add/save current credentials as a named account
@@ -16,6 +20,9 @@ pub fn add(name: &str, force: bool) -> Result<()> {
     // Load metadata
     let mut meta = AccountsMetadata::load()?;
 
+    // Names can't collide with aliases, even with --force
+    meta.check_not_alias(name)?;
+
     // Check if account already exists
     if meta.account_exists(name) && !force {
         return Err(SwitchError::AccountExists(name.to_string()).into());
@@ -26,31 +33,52 @@ pub fn add(name: &str, force: bool) -> Result<()> {
 
     // Save credentials to account file
     let account_path = paths::account_credentials_path(name)?;
-    creds.save_to(&account_path)?;
+    creds.save_to_store(&account_path)?;
 
-    // Update metadata
+    // Update metadata, keeping user-set fields such as notes when re-adding
+    let replaced = meta.account_exists(name);
     let now = chrono::Utc::now().timestamp_millis();
-    let info = AccountInfo {
-        added_at: now,
-        last_used_at: now,
-        subscription_type: creds.subscription_type().to_string(),
-        token_expires_at: creds.expires_at(),
-        notes: None,
+    let info = match meta.get_account(name) {
+        Some(existing) => AccountInfo {
+            last_used_at: now,
+            subscription_type: creds.subscription_type().to_string(),
+            token_expires_at: creds.expires_at(),
+            ..existing.clone()
+        },
+        None => AccountInfo {
+            added_at: now,
+            last_used_at: now,
+            subscription_type: creds.subscription_type().to_string(),
+            token_expires_at: creds.expires_at(),
+            ..Default::default()
+        },
     };
 
     meta.add_account(name.to_string(), info);
     meta.current_account = Some(name.to_string());
     meta.save()?;
 
+    let mut event = AuditEvent::new("add", Some(name)).fingerprint(name, &creds);
+    if replaced {
+        event = event.detail("replaced", "true");
+    }
+    event.record()?;
+
+    let config = Config::load()?;
+    hooks::run(
+        &config.hooks,
+        HookEvent::PostAdd,
+        &[
+            ("ACCOUNT", name.to_string()),
+            ("SUBSCRIPTION", creds.subscription_type().to_string()),
+        ],
+    )?;
+
     println!();
-    println!("{} Account '{}' saved successfully!", "✓".green(), name.cyan());
+    ui::success(format!("Account '{}' saved successfully!", name.cyan()));
     println!();
-    println!("  {} {}", "Subscription:".dimmed(), creds.subscription_type());
-    println!(
-        "  {} {}",
-        "Stored at:".dimmed(),
-        account_path.display().to_string().dimmed()
-    );
+    ui::field("Subscription:", creds.subscription_type());
+    ui::field("Stored at:", account_path.display().to_string().dimmed());
     println!();
 
     Ok(())
@@ -0,0 +1,421 @@
+use anyhow::Result;
+use crate::agent::{self, Request};
+use crate::error::SwitchError;
+use crate::store;
+use crate::timefmt::{self, countdown};
+use crate::ui;
+
+/// Hand the store key to the running agent
+pub fn unlock() -> Result<()> {
+    if !store::is_encrypted()? {
+        return Err(SwitchError::StoreNotEncrypted.into());
+    }
+    if agent::socket_from_env().is_none() {
+        return Err(SwitchError::AgentNotRunning.into());
+    }
+
+    let passphrase = store::read_passphrase("Store passphrase: ")?;
+    let key = store::unlock(&passphrase)?;
+    let response = agent::set_key(&key)?;
+
+    println!();
+    ui::success("Store unlocked");
+    if let Some(remaining) = response.idle_remaining_ms {
+        ui::hint(format!("The agent forgets the key after {} without use.", countdown(remaining)));
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Make the running agent forget the store key
+pub fn lock() -> Result<()> {
+    agent::request(&Request::Lock)?;
+    store::forget_key();
+
+    println!();
+    ui::success("Agent locked");
+    println!();
+
+    Ok(())
+}
+
+/// Run the unlock agent.
+///
+/// Like `ssh-agent`, it prints shell commands that export the socket
+/// path and then detaches, unless `foreground` is set.
+#[cfg(unix)]
+pub fn agent(timeout: &str, socket: Option<std::path::PathBuf>, foreground: bool) -> Result<()> {
+    if !server::PEER_CHECK {
+        anyhow::bail!("The unlock agent can't check who connects to it on this platform");
+    }
+    let idle_ms = timefmt::parse_duration(timeout)?;
+    let socket = match socket {
+        Some(path) => path,
+        None => server::default_socket()?,
+    };
+    let listener = server::bind(&socket)?;
+
+    if !foreground {
+        if let Some(child) = server::detach()? {
+            // Parent: tell the shell where the agent lives, then leave
+            print_exports(&socket, child);
+            return Ok(());
+        }
+    } else {
+        print_exports(&socket, std::process::id());
+    }
+
+    server::serve(listener, &socket, idle_ms)
+}
+
+/// The agent needs Unix sockets
+#[cfg(not(unix))]
+pub fn agent(_timeout: &str, _socket: Option<std::path::PathBuf>, _foreground: bool) -> Result<()> {
+    anyhow::bail!("The unlock agent is only available on Unix")
+}
+
+fn print_exports(socket: &std::path::Path, pid: u32) {
+    let quoted = socket.display().to_string().replace('\'', "'\\''");
+    println!("{}='{}'; export {};", agent::SOCKET_ENV, quoted, agent::SOCKET_ENV);
+    println!("echo claude-switch agent pid {};", pid);
+}
+
+#[cfg(unix)]
+mod server {
+    use anyhow::Result;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, MetadataExt, PermissionsExt};
+    use std::os::unix::io::AsRawFd;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+    use zeroize::{Zeroize, Zeroizing};
+    use crate::agent::{Request, Response};
+    use crate::store;
+
+    /// Longest request line accepted, well above a `set` request
+    const MAX_REQUEST: u64 = 4096;
+
+    static STOP: AtomicBool = AtomicBool::new(false);
+
+    /// The store key, pinned in RAM so it never reaches swap and wiped
+    /// when dropped
+    struct LockedKey {
+        bytes: Box<[u8; 32]>,
+        pinned: bool,
+    }
+
+    impl LockedKey {
+        fn new(key: [u8; 32]) -> Self {
+            let bytes = Box::new(key);
+            // SAFETY: the pointer and length describe the boxed array,
+            // which stays at the same address until it is dropped
+            let pinned = unsafe { libc::mlock(bytes.as_ptr().cast(), bytes.len()) == 0 };
+            Self { bytes, pinned }
+        }
+    }
+
+    impl Drop for LockedKey {
+        fn drop(&mut self) {
+            self.bytes.zeroize();
+            if self.pinned {
+                // SAFETY: unlocks exactly the range locked in `new`
+                unsafe {
+                    libc::munlock(self.bytes.as_ptr().cast(), self.bytes.len());
+                }
+            }
+        }
+    }
+
+    struct AgentState {
+        key: Option<LockedKey>,
+        last_used: Instant,
+        idle: Duration,
+    }
+
+    impl AgentState {
+        fn remaining(&self) -> Option<i64> {
+            self.key.as_ref()?;
+            let left = self.idle.saturating_sub(self.last_used.elapsed());
+            Some(left.as_millis() as i64)
+        }
+
+        fn expire(&mut self) {
+            if self.key.is_some() && self.last_used.elapsed() >= self.idle {
+                self.key = None;
+            }
+        }
+
+        fn handle(&mut self, request: Request) -> Response {
+            self.expire();
+            match request {
+                Request::Get => {
+                    let key = self.key.as_ref().map(|k| store::hex_encode(k.bytes.as_ref()));
+                    if key.is_some() {
+                        self.last_used = Instant::now();
+                    }
+                    self.status(key)
+                }
+                Request::Set { mut key } => {
+                    let bytes = store::hex_decode(&key).map(Zeroizing::new);
+                    key.zeroize();
+                    let Some(array) = bytes.and_then(|b| <[u8; 32]>::try_from(b.as_slice()).ok())
+                    else {
+                        return Response::error("invalid key");
+                    };
+                    self.key = Some(LockedKey::new(array));
+                    self.last_used = Instant::now();
+                    self.status(None)
+                }
+                Request::Lock => {
+                    self.key = None;
+                    self.status(None)
+                }
+                Request::Status => self.status(None),
+            }
+        }
+
+        fn status(&self, key: Option<String>) -> Response {
+            Response {
+                ok: true,
+                key,
+                unlocked: self.key.is_some(),
+                idle_remaining_ms: self.remaining(),
+                ..Response::default()
+            }
+        }
+    }
+
+    /// `$XDG_RUNTIME_DIR`, or a private directory under the temp dir
+    pub fn default_socket() -> Result<PathBuf> {
+        let dir = match std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => {
+                // SAFETY: getuid has no preconditions
+                let uid = unsafe { libc::getuid() };
+                let dir = std::env::temp_dir().join(format!("claude-switch-{}", uid));
+                if !dir.exists() {
+                    std::fs::DirBuilder::new().mode(0o700).create(&dir)?;
+                }
+                let meta = std::fs::symlink_metadata(&dir)?;
+                if !meta.is_dir() || meta.uid() != uid || meta.mode() & 0o077 != 0 {
+                    anyhow::bail!("{} is not a private directory of this user", dir.display());
+                }
+                dir
+            }
+        };
+        Ok(dir.join(format!("claude-switch-agent.{}.sock", std::process::id())))
+    }
+
+    /// Bind the socket so that only its owner can connect
+    ///
+    /// A stale socket left by an agent that died is replaced, but only if
+    /// it is a socket owned by this user; anything else at the path is left
+    /// alone.
+    pub fn bind(socket: &Path) -> Result<UnixListener> {
+        match std::fs::symlink_metadata(socket) {
+            Ok(meta) => {
+                if !meta.file_type().is_socket() {
+                    anyhow::bail!("{} exists and is not a socket", socket.display());
+                }
+                // SAFETY: getuid has no preconditions
+                if meta.uid() != unsafe { libc::getuid() } {
+                    anyhow::bail!("{} belongs to another user", socket.display());
+                }
+                if UnixStream::connect(socket).is_ok() {
+                    anyhow::bail!("An agent is already listening on {}", socket.display());
+                }
+                std::fs::remove_file(socket)?;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        // SAFETY: umask only changes this process's file creation mask
+        let old = unsafe { libc::umask(0o177) };
+        let listener = UnixListener::bind(socket);
+        // SAFETY: as above, restoring the previous mask
+        unsafe {
+            libc::umask(old);
+        }
+        let listener = listener?;
+        std::fs::set_permissions(socket, std::fs::Permissions::from_mode(0o600))?;
+        Ok(listener)
+    }
+
+    /// Fork into the background. Returns the child's pid in the parent.
+    pub fn detach() -> Result<Option<u32>> {
+        // SAFETY: the process is single-threaded at this point, so the
+        // child can keep running normal Rust code after fork
+        match unsafe { libc::fork() } {
+            -1 => Err(std::io::Error::last_os_error().into()),
+            0 => {
+                // SAFETY: setsid, open and dup2 have no memory-safety
+                // preconditions; the path is a valid C string
+                unsafe {
+                    libc::setsid();
+                    let null = libc::open(c"/dev/null".as_ptr(), libc::O_RDWR);
+                    if null >= 0 {
+                        for fd in 0..3 {
+                            libc::dup2(null, fd);
+                        }
+                        if null > 2 {
+                            libc::close(null);
+                        }
+                    }
+                }
+                Ok(None)
+            }
+            child => Ok(Some(child as u32)),
+        }
+    }
+
+    /// Answer requests until stopped by a signal
+    pub fn serve(listener: UnixListener, socket: &Path, idle_ms: i64) -> Result<()> {
+        harden();
+        install_stop_handlers();
+
+        let mut state = AgentState {
+            key: None,
+            last_used: Instant::now(),
+            idle: Duration::from_millis(idle_ms as u64),
+        };
+
+        while !STOP.load(Ordering::SeqCst) {
+            state.expire();
+
+            let mut fd = libc::pollfd {
+                fd: listener.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // SAFETY: fd points to one valid pollfd for the whole call
+            let ready = unsafe { libc::poll(&mut fd, 1, 1000) };
+            if ready <= 0 {
+                continue;
+            }
+
+            if let Ok((stream, _)) = listener.accept() {
+                if same_user(&stream) {
+                    let _ = answer(stream, &mut state);
+                }
+            }
+        }
+
+        drop(state);
+        let _ = std::fs::remove_file(socket);
+        Ok(())
+    }
+
+    fn answer(stream: UnixStream, state: &mut AgentState) -> Result<()> {
+        stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+        stream.set_write_timeout(Some(Duration::from_secs(2)))?;
+
+        let mut line = Zeroizing::new(String::new());
+        BufReader::new((&stream).take(MAX_REQUEST)).read_line(&mut line)?;
+
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => state.handle(request),
+            Err(_) => Response::error("invalid request"),
+        };
+
+        let mut reply = Zeroizing::new(serde_json::to_string(&response)?);
+        if let Some(mut key) = response.key {
+            key.zeroize();
+        }
+        reply.push('\n');
+        (&stream).write_all(reply.as_bytes())?;
+        Ok(())
+    }
+
+    /// Refuse peers running as another user, on top of the socket's mode
+    #[cfg(target_os = "linux")]
+    fn same_user(stream: &UnixStream) -> bool {
+        let mut cred = libc::ucred { pid: 0, uid: 0, gid: 0 };
+        let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+        // SAFETY: cred and len describe a buffer of the size SO_PEERCRED
+        // writes
+        let ok = unsafe {
+            libc::getsockopt(
+                stream.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_PEERCRED,
+                (&mut cred as *mut libc::ucred).cast(),
+                &mut len,
+            ) == 0
+        };
+        // SAFETY: getuid has no preconditions
+        ok && cred.uid == unsafe { libc::getuid() }
+    }
+
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly"
+    ))]
+    fn same_user(stream: &UnixStream) -> bool {
+        let mut uid = 0;
+        let mut gid = 0;
+        // SAFETY: getpeereid writes one uid and one gid
+        let ok = unsafe { libc::getpeereid(stream.as_raw_fd(), &mut uid, &mut gid) == 0 };
+        // SAFETY: getuid has no preconditions
+        ok && uid == unsafe { libc::getuid() }
+    }
+
+    /// Whether connecting peers can be checked on this platform; the agent
+    /// refuses to start where they can't
+    pub const PEER_CHECK: bool = cfg!(any(
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly"
+    ));
+
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly"
+    )))]
+    fn same_user(_stream: &UnixStream) -> bool {
+        false
+    }
+
+    /// Keep the key out of core dumps and away from debuggers
+    fn harden() {
+        let no_core = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+        // SAFETY: setrlimit reads one valid rlimit
+        unsafe {
+            libc::setrlimit(libc::RLIMIT_CORE, &no_core);
+        }
+        #[cfg(target_os = "linux")]
+        // SAFETY: PR_SET_DUMPABLE takes plain integer arguments
+        unsafe {
+            libc::prctl(libc::PR_SET_DUMPABLE, 0);
+        }
+    }
+
+    fn install_stop_handlers() {
+        extern "C" fn stop(_: libc::c_int) {
+            STOP.store(true, Ordering::SeqCst);
+        }
+        for signal in [libc::SIGTERM, libc::SIGINT, libc::SIGHUP] {
+            // SAFETY: the handler only stores to an atomic, which is
+            // async-signal-safe
+            unsafe {
+                libc::signal(signal, stop as *const () as libc::sighandler_t);
+            }
+        }
+    }
+}
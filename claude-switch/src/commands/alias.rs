@@ -0,0 +1,71 @@
+use anyhow::Result;
+use colored::Colorize;
+use crate::audit::AuditEvent;
+use crate::metadata::AccountsMetadata;
+use crate::table::Table;
+use crate::ui;
+
+/// Add an alias for an account
+pub fn alias_add(alias: &str, name: &str) -> Result<()> {
+    let mut meta = AccountsMetadata::load()?;
+
+    let name = meta.resolve_account(name)?;
+    meta.add_alias(alias, &name)?;
+    meta.save()?;
+    AuditEvent::new("alias-add", Some(&name))
+        .detail("alias", alias.to_lowercase())
+        .record()?;
+
+    println!();
+    ui::success(format!(
+        "'{}' is now an alias for '{}'",
+        alias.to_lowercase(),
+        name.cyan()
+    ));
+    println!();
+
+    Ok(())
+}
+
+/// Remove an alias
+pub fn alias_remove(alias: &str) -> Result<()> {
+    let mut meta = AccountsMetadata::load()?;
+
+    let target = meta.remove_alias(alias)?;
+    meta.save()?;
+    AuditEvent::new("alias-remove", Some(&target))
+        .detail("alias", alias.to_lowercase())
+        .record()?;
+
+    println!();
+    ui::success(format!(
+        "Removed alias '{}' for '{}'",
+        alias.to_lowercase(),
+        target.cyan()
+    ));
+    println!();
+
+    Ok(())
+}
+
+/// List all aliases
+pub fn alias_list() -> Result<()> {
+    let meta = AccountsMetadata::load()?;
+
+    println!();
+    if meta.aliases.is_empty() {
+        ui::hint("No aliases yet. Use 'claude-switch alias add <alias> <name>' to create one.");
+        println!();
+        return Ok(());
+    }
+
+    let mut table = Table::new(["ALIAS", "ACCOUNT"]);
+    for (alias, target) in &meta.aliases {
+        table.add_row(vec![alias.clone(), target.cyan().to_string()]);
+    }
+
+    ui::table("Aliases", &table);
+    println!();
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use colored::Colorize;
+use crate::audit;
+use crate::table::Table;
+use crate::timefmt::local_time;
+use crate::ui;
+
+/// Check that the audit log has not been edited or truncated
+pub fn audit_verify() -> Result<()> {
+    let count = audit::verify()?;
+
+    println!();
+    if count == 0 {
+        ui::info("Audit log is empty");
+    } else {
+        ui::success(format!("Audit log intact ({} entries, hash chain verified)", count));
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Show the most recent audit entries
+pub fn audit_list(limit: usize) -> Result<()> {
+    let mut entries = audit::load()?;
+    if limit > 0 && entries.len() > limit {
+        entries.drain(..entries.len() - limit);
+    }
+
+    println!();
+    if entries.is_empty() {
+        ui::hint("No audit entries recorded yet.");
+        println!();
+        return Ok(());
+    }
+
+    let mut table = Table::new(vec!["#", "WHEN", "USER", "PID", "ACTION", "ACCOUNT", "DETAILS"]);
+    for entry in &entries {
+        let body = &entry.body;
+        let details: Vec<String> = body
+            .details
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .chain(body.fingerprints.iter().map(|(k, v)| format!("{}:{}", k, v)))
+            .collect();
+        table.add_row(vec![
+            body.seq.to_string(),
+            local_time(body.at),
+            body.user.clone(),
+            body.pid.to_string(),
+            body.action.clone(),
+            body.account.as_deref().unwrap_or("-").cyan().to_string(),
+            details.join(" ").dimmed().to_string(),
+        ]);
+    }
+
+    ui::table("Audit Log", &table);
+    println!();
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anyhow::Result;
+use colored::Colorize;
+use crate::commands::switch_account;
+use crate::config::Config;
+use crate::history::Trigger;
+use crate::metadata::AccountsMetadata;
+use crate::rules;
+use crate::ui;
+
+/// Switch to the account the time-based rules select right now
+pub fn auto() -> Result<()> {
+    let config = Config::load()?;
+    if config.rules.is_empty() {
+        println!();
+        ui::info("No rules configured");
+        ui::hint("  Add rules to ~/.claude/accounts/config.json, see 'claude-switch rules list'");
+        println!();
+        return Ok(());
+    }
+
+    let meta = AccountsMetadata::load()?;
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let Some((index, account)) = rules::select(&config.rules, &meta, now)? else {
+        println!();
+        ui::info("No rule applies right now");
+        println!();
+        return Ok(());
+    };
+    let label = config.rules[index].label(index);
+
+    if meta.current_account.as_deref() == Some(account.as_str()) {
+        println!();
+        ui::info(format!("Already using '{}' (rule '{}')", account.cyan(), label));
+        println!();
+        return Ok(());
+    }
+
+    if let Some(remaining) = meta.borrow.as_ref().and_then(|b| b.remaining()) {
+        println!();
+        ui::info(format!(
+            "Rule '{}' selects '{}', but a borrow has {} left; not switching",
+            label,
+            account.cyan(),
+            crate::timefmt::countdown(remaining)
+        ));
+        println!();
+        return Ok(());
+    }
+
+    println!();
+    ui::info(format!("Rule '{}' selects '{}'", label, account.cyan()));
+    switch_account(&account, Trigger::Auto)
+}
@@ -2,6 +2,8 @@ use anyhow::Result;
 use colored::Colorize;
 use crate::credentials::{Credentials, TokenStatus};
 use crate::metadata::AccountsMetadata;
+use crate::timefmt::countdown;
+use crate::ui;
 
 /// Show current account info
 pub fn current() -> Result<()> {
@@ -13,18 +15,35 @@ pub fn current() -> Result<()> {
     let status = TokenStatus::from_expires_at(creds.expires_at());
 
     println!();
-    println!("{}", "Current Account".bold());
-    println!("{}", "─".repeat(40));
-    println!("  {} {}", "Account:".dimmed(), account_name.cyan());
-    println!("  {} {}", "Subscription:".dimmed(), subscription);
-    println!("  {} {}", "Token Status:".dimmed(), status.display());
+    ui::heading("Current Account", 40);
+    ui::field("Account:", account_name.cyan());
+    ui::field("Subscription:", subscription);
+    ui::field("Token Status:", status.display());
+
+    let cooldown = meta
+        .current_account
+        .as_deref()
+        .and_then(|name| meta.get_account(name))
+        .and_then(|info| info.cooldown_remaining());
+    if let Some(remaining) = cooldown {
+        ui::field(
+            "Rate limited:",
+            format!("{} left", countdown(remaining)).yellow(),
+        );
+    }
+
+    if let Some(borrow) = &meta.borrow {
+        let remaining = countdown(borrow.remaining().unwrap_or(0));
+        let value = match &borrow.return_to {
+            Some(return_to) => format!("{} left, then back to '{}'", remaining, return_to),
+            None => format!("{} left", remaining),
+        };
+        ui::field("Borrowed:", value.yellow());
+    }
     println!();
 
     if meta.current_account.is_none() {
-        println!(
-            "{}",
-            "Tip: Use 'claude-switch add <name>' to save this account.".dimmed()
-        );
+        ui::hint("Tip: Use 'claude-switch add <name>' to save this account.");
         println!();
     }
 
@@ -0,0 +1,132 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use crate::commands::{revert_expired_borrow, scan_limits, switch_account};
+use crate::config::Config;
+use crate::credentials::TokenStatus;
+use crate::history::Trigger;
+use crate::hooks::{self, HookEvent};
+use crate::metadata::AccountsMetadata;
+use crate::rules;
+use crate::timefmt::{self, countdown, local_time};
+use crate::ui;
+
+/// State carried between daemon rounds
+#[derive(Debug, Default)]
+struct DaemonState {
+    /// Account the rules selected last round. Rules only switch when their
+    /// selection changes, so a manual switch isn't undone a minute later.
+    rule_target: Option<String>,
+
+    /// Token expiry already warned about, per account, so the
+    /// `on-expiry-warning` hook fires once per token
+    expiry_warned: HashMap<String, i64>,
+}
+
+/// Keep running periodic maintenance tasks until interrupted
+pub fn daemon(interval: &str) -> Result<()> {
+    let interval_ms = timefmt::parse_duration(interval)?;
+
+    ui::info(format!(
+        "claude-switch daemon running every {} (Ctrl-C to stop)",
+        interval
+    ));
+
+    let mut state = DaemonState::default();
+    loop {
+        if let Err(e) = tick(&mut state) {
+            log(format!("{} {}", ui::error_mark(), e));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(interval_ms as u64));
+    }
+}
+
+/// One round of maintenance
+fn tick(state: &mut DaemonState) -> Result<()> {
+    for limit in scan_limits::scan(false)? {
+        log(format!("{} {}", ui::ok_mark(), scan_limits::describe(&limit)));
+    }
+    if let Some(account) = revert_expired_borrow()? {
+        log(format!("{} Borrow ended, switched back to '{}'", ui::ok_mark(), account));
+    }
+    warn_expiring(state)?;
+    apply_rules(state)?;
+    Ok(())
+}
+
+/// Run the `on-expiry-warning` hook for tokens that expire within a day
+fn warn_expiring(state: &mut DaemonState) -> Result<()> {
+    let config = Config::load()?;
+    let meta = AccountsMetadata::load()?;
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let mut names: Vec<&String> = meta.accounts.keys().collect();
+    names.sort();
+    for name in names {
+        let expires_at = meta.accounts[name].token_expires_at;
+        if !matches!(TokenStatus::from_expires_at(expires_at), TokenStatus::Warning { .. }) {
+            continue;
+        }
+        if state.expiry_warned.get(name) == Some(&expires_at) {
+            continue;
+        }
+        state.expiry_warned.insert(name.clone(), expires_at);
+
+        log(format!(
+            "{} Token for '{}' expires in {} ({})",
+            ui::warn_mark(),
+            name,
+            countdown(expires_at - now),
+            local_time(expires_at)
+        ));
+        hooks::run(
+            &config.hooks,
+            HookEvent::OnExpiryWarning,
+            &[
+                ("ACCOUNT", name.clone()),
+                ("EXPIRES_AT", expires_at.to_string()),
+                ("EXPIRES_IN", countdown(expires_at - now)),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Switch to the account the rules select, when that selection changes
+fn apply_rules(state: &mut DaemonState) -> Result<()> {
+    let config = Config::load()?;
+    if config.rules.is_empty() {
+        return Ok(());
+    }
+
+    let meta = AccountsMetadata::load()?;
+    let now = chrono::Utc::now().timestamp_millis();
+    let selected = rules::select(&config.rules, &meta, now)?;
+    let target = selected.as_ref().map(|(_, account)| account.clone());
+
+    if target == state.rule_target {
+        return Ok(());
+    }
+    state.rule_target = target;
+
+    let Some((index, account)) = selected else {
+        return Ok(());
+    };
+    let borrowing = meta.borrow.as_ref().is_some_and(|b| b.remaining().is_some());
+    if meta.current_account.as_deref() == Some(account.as_str()) || borrowing {
+        return Ok(());
+    }
+
+    switch_account(&account, Trigger::Auto)?;
+    log(format!(
+        "{} Rule '{}' switched to '{}'",
+        ui::ok_mark(),
+        config.rules[index].label(index),
+        account
+    ));
+    Ok(())
+}
+
+/// Print a line prefixed with the local time
+fn log(msg: String) {
+    println!("[{}] {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"), msg);
+}
@@ -0,0 +1,134 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::path::PathBuf;
+use zeroize::Zeroizing;
+use crate::audit::AuditEvent;
+use crate::error::SwitchError;
+use crate::paths;
+use crate::store;
+use crate::ui;
+
+/// Encrypt every saved credentials file with a passphrase.
+///
+/// Re-running on an encrypted store asks for the existing passphrase and
+/// encrypts any files still in plaintext, so an interrupted run can be
+/// finished.
+pub fn encrypt() -> Result<()> {
+    let key = if store::is_encrypted()? {
+        let passphrase = store::read_passphrase("Store passphrase: ")?;
+        store::unlock(&passphrase)?
+    } else {
+        let passphrase = new_passphrase()?;
+        store::create(&passphrase)?
+    };
+
+    let mut converted = 0;
+    for path in store_files()? {
+        let contents = Zeroizing::new(std::fs::read_to_string(&path)?);
+        if store::is_envelope(&contents) {
+            continue;
+        }
+        // Refuse to encrypt something that isn't credentials JSON
+        serde_json::from_str::<serde_json::Value>(&contents)?;
+        store::write_private(&path, &store::encrypt(&key, contents.as_bytes())?)?;
+        converted += 1;
+    }
+
+    AuditEvent::new("encrypt", None)
+        .detail("files", converted.to_string())
+        .record()?;
+
+    println!();
+    ui::success(format!(
+        "Account store encrypted ({} {} converted)",
+        converted,
+        if converted == 1 { "file" } else { "files" }
+    ));
+    ui::hint(format!(
+        "Commands that read saved credentials will ask for the passphrase, or read {}.",
+        store::PASSPHRASE_ENV.cyan()
+    ));
+    println!();
+
+    Ok(())
+}
+
+/// Decrypt every saved credentials file and turn encryption off
+pub fn decrypt() -> Result<()> {
+    if !store::is_encrypted()? {
+        return Err(SwitchError::StoreNotEncrypted.into());
+    }
+
+    let passphrase = store::read_passphrase("Store passphrase: ")?;
+    let key = store::unlock(&passphrase)?;
+
+    // Decrypt everything before touching any file, so a corrupt file
+    // leaves the store as it was
+    let mut plaintexts = Vec::new();
+    for path in store_files()? {
+        let contents = std::fs::read_to_string(&path)?;
+        if store::is_envelope(&contents) {
+            let plaintext = store::decrypt(&key, &contents, &path)?;
+            let text = Zeroizing::new(String::from_utf8_lossy(&plaintext).into_owned());
+            plaintexts.push((path, text));
+        }
+    }
+
+    for (path, text) in &plaintexts {
+        store::write_private(path, text)?;
+    }
+    // Removed last: while it exists, an interrupted run can be retried
+    store::remove_header()?;
+
+    AuditEvent::new("decrypt", None)
+        .detail("files", plaintexts.len().to_string())
+        .record()?;
+
+    println!();
+    ui::success(format!(
+        "Account store decrypted ({} {} converted)",
+        plaintexts.len(),
+        if plaintexts.len() == 1 { "file" } else { "files" }
+    ));
+    println!();
+
+    Ok(())
+}
+
+/// Ask for a new passphrase, twice when typed interactively
+fn new_passphrase() -> Result<Zeroizing<String>> {
+    let passphrase = store::read_passphrase("New store passphrase: ")?;
+    if passphrase.is_empty() {
+        return Err(SwitchError::EmptyPassphrase.into());
+    }
+    if std::env::var(store::PASSPHRASE_ENV).is_err() {
+        let confirm = store::read_passphrase("Repeat passphrase: ")?;
+        if *confirm != *passphrase {
+            return Err(SwitchError::PassphraseMismatch.into());
+        }
+    }
+    Ok(passphrase)
+}
+
+/// Saved credentials files plus the backup of the last active credentials
+fn store_files() -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    let dir = paths::credentials_dir()?;
+    if dir.exists() {
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+
+    let backup = paths::backup_path()?;
+    if backup.exists() {
+        files.push(backup);
+    }
+
+    Ok(files)
+}
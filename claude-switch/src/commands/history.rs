@@ -0,0 +1,65 @@
+use anyhow::Result;
+use colored::Colorize;
+use crate::history::SwitchRecord;
+use crate::metadata::AccountsMetadata;
+use crate::table::Table;
+use crate::timefmt::{self, local_time, relative_time};
+use crate::ui;
+
+/// Show recorded account switches, oldest first
+pub fn history(account: Option<&str>, since: Option<&str>, limit: usize) -> Result<()> {
+    let since = since.map(timefmt::parse_since).transpose()?;
+    let account = match account {
+        Some(input) => {
+            let meta = AccountsMetadata::load()?;
+            // Removed accounts still appear in history under their old name
+            Some(meta.resolve_account(input).unwrap_or_else(|_| input.to_string()))
+        }
+        None => None,
+    };
+
+    let mut records: Vec<SwitchRecord> = crate::history::load()?
+        .into_iter()
+        .filter(|r| since.is_none_or(|since| r.at >= since))
+        .filter(|r| {
+            account.as_deref().is_none_or(|a| r.to == a || r.from.as_deref() == Some(a))
+        })
+        .collect();
+
+    let total = records.len();
+    if limit > 0 && records.len() > limit {
+        records.drain(..records.len() - limit);
+    }
+
+    println!();
+    if records.is_empty() {
+        ui::hint("No account switches recorded for this period.");
+        println!();
+        return Ok(());
+    }
+
+    let mut table = Table::new(vec!["WHEN", "", "FROM", "TO", "TRIGGER", "HOST"]);
+    for record in &records {
+        table.add_row(vec![
+            local_time(record.at),
+            relative_time(record.at).dimmed().to_string(),
+            record.from.clone().unwrap_or_else(|| "-".to_string()),
+            record.to.cyan().to_string(),
+            record.trigger.label().to_string(),
+            record.host.clone().unwrap_or_default().dimmed().to_string(),
+        ]);
+    }
+
+    ui::table("Switch History", &table);
+    println!();
+    if records.len() < total {
+        ui::hint(format!(
+            "  Showing the last {} of {} switches (use --limit 0 for all)",
+            records.len(),
+            total
+        ));
+        println!();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+use crate::paths;
+use crate::ui;
+use crate::error::SwitchError;
+
+/// Point Claude Code's `statusLine` setting at `claude-switch statusline`
+pub fn install_statusline(config_dir: Option<PathBuf>, force: bool) -> Result<()> {
+    let settings_path = match config_dir {
+        Some(dir) => dir.join("settings.json"),
+        None => paths::settings_path()?,
+    };
+
+    // Load existing settings, keeping every other key untouched
+    let mut settings = if settings_path.exists() {
+        let contents = std::fs::read_to_string(&settings_path)?;
+        if contents.trim().is_empty() {
+            Map::new()
+        } else {
+            match serde_json::from_str::<Value>(&contents)? {
+                Value::Object(map) => map,
+                _ => {
+                    return Err(SwitchError::InvalidSettings(
+                        settings_path.display().to_string(),
+                    )
+                    .into())
+                }
+            }
+        }
+    } else {
+        Map::new()
+    };
+
+    let command = statusline_command()?;
+
+    if let Some(existing) = settings.get("statusLine") {
+        let existing_command = existing.get("command").and_then(Value::as_str).unwrap_or("");
+        if existing_command == command {
+            println!();
+            ui::info("Status line already installed");
+            println!();
+            return Ok(());
+        }
+        if !force {
+            return Err(SwitchError::StatusLineConfigured(existing_command.to_string()).into());
+        }
+    }
+
+    settings.insert(
+        "statusLine".to_string(),
+        json!({
+            "type": "command",
+            "command": command,
+            "padding": 0,
+        }),
+    );
+
+    let contents = serde_json::to_string_pretty(&Value::Object(settings))?;
+    write_settings(&settings_path, &(contents + "\n"))?;
+
+    println!();
+    ui::success("Status line installed");
+    println!();
+    ui::field("Settings:", settings_path.display().to_string().dimmed());
+    ui::field("Command:", &command);
+    println!();
+
+    Ok(())
+}
+
+/// Absolute path to this binary, so the status line works regardless of PATH
+fn statusline_command() -> Result<String> {
+    let exe = std::env::current_exe()?;
+    let exe = exe.display().to_string();
+    Ok(format!("{} statusline", shell_quote(&exe)))
+}
+
+/// Quote a word for `sh` with single quotes, unless it is plainly safe
+fn shell_quote(word: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "/._-+:@%,=".contains(c);
+    if !word.is_empty() && word.chars().all(safe) {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+/// Replace `settings.json` atomically, keeping its permissions and
+/// writing through a symlink rather than replacing it
+fn write_settings(path: &Path, contents: &str) -> Result<()> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, contents)?;
+    if let Ok(meta) = std::fs::metadata(&path) {
+        std::fs::set_permissions(&tmp, meta.permissions())?;
+    }
+    std::fs::rename(&tmp, &path)?;
+
+    Ok(())
+}
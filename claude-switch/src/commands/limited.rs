@@ -0,0 +1,52 @@
+use anyhow::Result;
+use colored::Colorize;
+use crate::audit::AuditEvent;
+use crate::metadata::AccountsMetadata;
+use crate::timefmt::{self, local_time, relative_time};
+use crate::ui;
+use crate::error::SwitchError;
+
+/// Cooldown applied when neither `--until` nor `--for` is given (one
+/// usage window)
+const DEFAULT_COOLDOWN: &str = "5h";
+
+/// Mark an account as rate-limited until a time, or clear the mark
+pub fn limited(name: &str, until: Option<&str>, duration: Option<&str>, clear: bool) -> Result<()> {
+    let mut meta = AccountsMetadata::load()?;
+    let name = meta.resolve_account(name)?;
+
+    let limited_until = if clear {
+        None
+    } else if let Some(until) = until {
+        let at = timefmt::parse_time(until)?;
+        if at <= chrono::Utc::now().timestamp_millis() {
+            return Err(SwitchError::TimeInPast(until.to_string()).into());
+        }
+        Some(at)
+    } else {
+        let duration = timefmt::parse_duration(duration.unwrap_or(DEFAULT_COOLDOWN))?;
+        Some(chrono::Utc::now().timestamp_millis() + duration)
+    };
+
+    if let Some(info) = meta.accounts.get_mut(&name) {
+        info.limited_until = limited_until;
+    }
+    meta.save()?;
+    AuditEvent::new("limited", Some(&name))
+        .detail("until", limited_until.map_or("cleared".to_string(), local_time))
+        .record()?;
+
+    println!();
+    match limited_until {
+        Some(until) => ui::success(format!(
+            "Marked '{}' as rate-limited until {} ({})",
+            name.cyan(),
+            local_time(until),
+            relative_time(until)
+        )),
+        None => ui::success(format!("Cleared rate limit for '{}'", name.cyan())),
+    }
+    println!();
+
+    Ok(())
+}
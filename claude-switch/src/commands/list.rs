@@ -1,59 +1,306 @@
 use anyhow::Result;
+use clap::ValueEnum;
 use colored::Colorize;
-use crate::credentials::TokenStatus;
-use crate::metadata::AccountsMetadata;
+use crate::credentials::{Credentials, TokenStatus};
+use crate::metadata::{AccountInfo, AccountsMetadata};
+use crate::paths;
+use crate::table::{truncate, Table};
+use crate::timefmt::{countdown, relative_and_local, relative_time};
+use crate::ui;
 use crate::error::SwitchError;
 
+/// Longest notes excerpt shown in the table
+const NOTES_WIDTH: usize = 40;
+
+/// Sort order for `list`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SortKey {
+    Name,
+    /// Most recently used first
+    #[value(alias = "recent")]
+    LastUsed,
+    /// Oldest first
+    Added,
+    /// Soonest token expiry first
+    Expiry,
+    Type,
+}
+
+/// Columns `list` can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Column {
+    Name,
+    Type,
+    Status,
+    Tier,
+    Expires,
+    Added,
+    LastUsed,
+    Cooldown,
+    Aliases,
+    Tags,
+    Notes,
+}
+
+impl Column {
+    /// Columns shown when `--columns` is not given
+    pub const DEFAULT: &'static [Column] = &[
+        Column::Name,
+        Column::Type,
+        Column::Status,
+        Column::LastUsed,
+    ];
+
+    fn header(self) -> &'static str {
+        match self {
+            Column::Name => "NAME",
+            Column::Type => "TYPE",
+            Column::Status => "TOKEN STATUS",
+            Column::Tier => "RATE LIMIT TIER",
+            Column::Expires => "EXPIRES",
+            Column::Added => "ADDED",
+            Column::LastUsed => "LAST USED",
+            Column::Cooldown => "COOLDOWN",
+            Column::Aliases => "ALIASES",
+            Column::Tags => "TAGS",
+            Column::Notes => "NOTES",
+        }
+    }
+}
+
+/// Options controlling which accounts `list` shows and how
+#[derive(Debug, Clone)]
+pub struct ListOptions {
+    pub sort: SortKey,
+    pub reverse: bool,
+    pub filters: Vec<String>,
+    pub columns: Vec<Column>,
+    /// Show absolute local times next to relative ones
+    pub long: bool,
+}
+
+/// A single `key=value` / `key!=value` filter expression
+#[derive(Debug, Clone)]
+struct Filter {
+    key: FilterKey,
+    value: String,
+    negate: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FilterKey {
+    Name,
+    Type,
+    Status,
+    Tier,
+    Tag,
+}
+
+impl Filter {
+    fn parse(expr: &str) -> Result<Self, SwitchError> {
+        let invalid = || SwitchError::InvalidFilter(expr.to_string());
+
+        let (key, value, negate) = match expr.split_once("!=") {
+            Some((k, v)) => (k, v, true),
+            None => {
+                let (k, v) = expr.split_once('=').ok_or_else(invalid)?;
+                (k, v, false)
+            }
+        };
+
+        let key = match key.trim().to_lowercase().as_str() {
+            "name" => FilterKey::Name,
+            "type" => FilterKey::Type,
+            "status" => FilterKey::Status,
+            "tier" => FilterKey::Tier,
+            "tag" => FilterKey::Tag,
+            _ => return Err(invalid()),
+        };
+
+        Ok(Self {
+            key,
+            value: value.trim().to_lowercase(),
+            negate,
+        })
+    }
+
+    fn matches(&self, row: &Row) -> bool {
+        let actual = match self.key {
+            FilterKey::Name => vec![row.name.to_lowercase()],
+            FilterKey::Type => vec![row.info.subscription_type.to_lowercase()],
+            FilterKey::Status => vec![row.status.label().to_string()],
+            FilterKey::Tier => vec![row.tier().unwrap_or_default().to_lowercase()],
+            FilterKey::Tag => row.info.tags.iter().cloned().collect(),
+        };
+
+        let matched = actual.iter().any(|value| self.matches_value(value));
+        matched != self.negate
+    }
+
+    /// A trailing `*` matches by prefix
+    fn matches_value(&self, actual: &str) -> bool {
+        match self.value.strip_suffix('*') {
+            Some(prefix) => actual.starts_with(prefix),
+            None => actual == self.value,
+        }
+    }
+}
+
+/// One account with everything the columns and filters may need
+struct Row<'a> {
+    name: &'a str,
+    info: &'a AccountInfo,
+    status: TokenStatus,
+    creds: Option<Credentials>,
+}
+
+impl Row<'_> {
+    fn tier(&self) -> Option<String> {
+        self.creds
+            .as_ref()
+            .map(|c| c.claude_ai_oauth.rate_limit_tier.clone())
+    }
+}
+
 /// List all saved accounts
-pub fn list() -> Result<()> {
+pub fn list(options: &ListOptions) -> Result<()> {
     let meta = AccountsMetadata::load()?;
 
     if !meta.has_accounts() {
         return Err(SwitchError::NoAccountsSaved.into());
     }
 
-    let current = meta.current_account.as_deref();
+    let filters = options
+        .filters
+        .iter()
+        .map(|f| Filter::parse(f))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    println!();
-    println!("{}", "Saved Accounts".bold());
-    println!("{}", "─".repeat(60));
-    println!(
-        "  {:<2} {:<15} {:<10} {}",
-        "", "NAME".dimmed(), "TYPE".dimmed(), "TOKEN STATUS".dimmed()
-    );
-    println!("{}", "─".repeat(60));
+    let mut columns = if options.columns.is_empty() {
+        Column::DEFAULT.to_vec()
+    } else {
+        options.columns.clone()
+    };
 
-    // Sort accounts by name
-    let mut accounts: Vec<_> = meta.accounts.iter().collect();
-    accounts.sort_by(|a, b| a.0.cmp(b.0));
+    let current = meta.current_account.as_deref();
 
-    for (name, info) in accounts {
-        let is_current = current == Some(name.as_str());
-        let marker = if is_current { "●".green().to_string() } else { " ".to_string() };
-        let status = TokenStatus::from_expires_at(info.token_expires_at);
+    let mut rows: Vec<Row> = meta
+        .accounts
+        .iter()
+        .map(|(name, info)| Row {
+            name,
+            info,
+            status: TokenStatus::from_expires_at(info.token_expires_at),
+            creds: paths::account_credentials_path(name)
+                .ok()
+                .and_then(|p| Credentials::peek_from(&p)),
+        })
+        .filter(|row| filters.iter().all(|f| f.matches(row)))
+        .collect();
 
-        let name_display = if is_current {
-            name.cyan().bold().to_string()
-        } else {
-            name.to_string()
+    rows.sort_by(|a, b| {
+        let ordering = match options.sort {
+            SortKey::Name => a.name.cmp(b.name),
+            SortKey::LastUsed => b.info.last_used_at.cmp(&a.info.last_used_at),
+            SortKey::Added => a.info.added_at.cmp(&b.info.added_at),
+            SortKey::Expiry => a.info.token_expires_at.cmp(&b.info.token_expires_at),
+            SortKey::Type => a.info.subscription_type.cmp(&b.info.subscription_type),
         };
+        ordering.then_with(|| a.name.cmp(b.name))
+    });
+    if options.reverse {
+        rows.reverse();
+    }
+
+    if rows.is_empty() {
+        println!();
+        ui::hint("No accounts match the given filters.");
+        println!();
+        return Ok(());
+    }
+
+    // Cooldowns, aliases and notes are shown by default once any account has one
+    if options.columns.is_empty() {
+        if rows.iter().any(|row| row.info.cooldown_remaining().is_some()) {
+            columns.push(Column::Cooldown);
+        }
+        if !meta.aliases.is_empty() {
+            columns.push(Column::Aliases);
+        }
+        if rows.iter().any(|row| row.info.note().is_some()) {
+            columns.push(Column::Notes);
+        }
+    }
+
+    let mut table = Table::new(
+        std::iter::once("").chain(columns.iter().map(|c| c.header())),
+    );
 
-        println!(
-            "  {:<2} {:<15} {:<10} {}",
-            marker,
-            name_display,
-            info.subscription_type,
-            status.display()
+    for row in &rows {
+        let is_current = current == Some(row.name);
+        let marker = if is_current { ui::active_mark().to_string() } else { String::new() };
+
+        let mut cells = vec![marker];
+        cells.extend(
+            columns
+                .iter()
+                .map(|column| cell(*column, row, &meta, is_current, options.long)),
         );
+        table.add_row(cells);
     }
 
-    println!("{}", "─".repeat(60));
+    println!();
+    ui::table("Saved Accounts", &table);
     println!();
     println!(
         "{}",
-        format!("  {} = active account", "●".green()).dimmed()
+        format!("  {} = active account", ui::active_mark()).dimmed()
     );
     println!();
 
     Ok(())
 }
+
+fn cell(
+    column: Column,
+    row: &Row,
+    meta: &AccountsMetadata,
+    is_current: bool,
+    long: bool,
+) -> String {
+    let time = |ms: i64| if long { relative_and_local(ms) } else { relative_time(ms) };
+
+    match column {
+        Column::Name => {
+            if is_current {
+                row.name.cyan().bold().to_string()
+            } else {
+                row.name.to_string()
+            }
+        }
+        Column::Type => row.info.subscription_type.clone(),
+        Column::Status => row.status.display(),
+        Column::Tier => row.tier().unwrap_or_else(|| "-".to_string()),
+        Column::Expires => time(row.info.token_expires_at),
+        Column::Added => time(row.info.added_at),
+        Column::LastUsed => time(row.info.last_used_at),
+        Column::Cooldown => row
+            .info
+            .cooldown_remaining()
+            .map(|ms| format!("{} {} left", ui::glyphs().warn, countdown(ms)).yellow().to_string())
+            .unwrap_or_default(),
+        Column::Aliases => meta.aliases_for(row.name).join(","),
+        Column::Tags => row
+            .info
+            .tags
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(","),
+        Column::Notes => row
+            .info
+            .note()
+            .map(|n| truncate(n.lines().next().unwrap_or(""), NOTES_WIDTH))
+            .unwrap_or_default(),
+    }
+}
@@ -1,13 +1,55 @@
 pub mod add;
+pub mod agent;
+pub mod alias;
+pub mod audit;
+pub mod auto;
 pub mod current;
+pub mod daemon;
+pub mod encrypt;
+pub mod history;
+pub mod install_statusline;
+pub mod limited;
 pub mod list;
+pub mod note;
 pub mod remove;
 pub mod rename;
+pub mod rotate;
+pub mod rules;
+pub mod run;
+pub mod scan_limits;
+pub mod show;
+pub mod stats;
+pub mod status;
+pub mod statusline;
+pub mod suggest;
+pub mod tag;
+pub mod usage;
 pub mod use_account;
 
 pub use add::add;
+pub use agent::{agent, lock, unlock};
+pub use alias::{alias_add, alias_list, alias_remove};
+pub use audit::{audit_list, audit_verify};
+pub use auto::auto;
 pub use current::current;
+pub use daemon::daemon;
+pub use encrypt::{decrypt, encrypt};
+pub use history::history;
+pub use install_statusline::install_statusline;
+pub use limited::limited;
 pub use list::list;
+pub use note::note;
 pub use remove::remove;
 pub use rename::rename;
-pub use use_account::use_account;
+pub use rotate::{next, prev};
+pub use rules::{rules_list, rules_test};
+pub use run::run;
+pub use scan_limits::scan_limits;
+pub use show::show;
+pub use stats::stats;
+pub use status::status;
+pub use statusline::statusline;
+pub use suggest::suggest;
+pub use tag::{tag_add, tag_list, tag_remove};
+pub use usage::usage;
+pub use use_account::{borrow_account, revert_expired_borrow, switch_account, use_account, use_tag};
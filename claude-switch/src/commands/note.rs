@@ -0,0 +1,95 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::io::Write;
+use std::process::Command;
+use crate::audit::AuditEvent;
+use crate::metadata::AccountsMetadata;
+use crate::ui;
+use crate::error::SwitchError;
+
+/// Show, set, edit or clear the note attached to an account
+pub fn note(name: &str, text: Option<&str>, edit: bool, clear: bool) -> Result<()> {
+    let mut meta = AccountsMetadata::load()?;
+
+    let name = meta.resolve_account(name)?;
+    let name = name.as_str();
+    let Some(info) = meta.accounts.get_mut(name) else {
+        return Err(SwitchError::AccountNotFound(name.to_string()).into());
+    };
+
+    let new_note = if clear {
+        None
+    } else if edit {
+        Some(edit_in_editor(name, info.note().unwrap_or(""))?)
+    } else if let Some(text) = text {
+        Some(text.to_string())
+    } else {
+        // No changes requested: just print the note
+        println!();
+        match info.note() {
+            Some(note) => println!("{}", note),
+            None => ui::hint(format!("No note for '{}'", name)),
+        }
+        println!();
+        return Ok(());
+    };
+
+    info.notes = new_note
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+    let cleared = info.notes.is_none();
+    meta.save()?;
+    AuditEvent::new("note", Some(name))
+        .detail("note", if cleared { "cleared" } else { "set" })
+        .record()?;
+
+    println!();
+    if cleared {
+        ui::success(format!("Cleared note for '{}'", name.cyan()));
+    } else {
+        ui::success(format!("Updated note for '{}'", name.cyan()));
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Open `$VISUAL`/`$EDITOR` on the current note and return the edited text
+fn edit_in_editor(name: &str, current: &str) -> Result<String> {
+    let editor = std::env::var("VISUAL")
+        .ok()
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+
+    // A fresh private directory, so nobody else can swap the file for a
+    // symlink or read the note while it is being edited
+    let mut builder = tempfile::Builder::new();
+    builder.prefix("claude-switch-note-");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        builder.permissions(std::fs::Permissions::from_mode(0o700));
+    }
+    let dir = builder.tempdir()?;
+    let path = dir.path().join(format!("{}.txt", name));
+    let mut file = std::fs::OpenOptions::new();
+    file.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        file.mode(0o600);
+    }
+    file.open(&path)?.write_all(current.as_bytes())?;
+
+    // Allow editors configured with arguments, e.g. "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = Command::new(program).args(parts).arg(&path).status();
+
+    // The directory is removed when `dir` goes out of scope
+    match status {
+        Ok(status) if status.success() => Ok(std::fs::read_to_string(&path)?),
+        _ => Err(SwitchError::EditorFailed(editor).into()),
+    }
+}
@@ -1,7 +1,12 @@
 use anyhow::Result;
 use colored::Colorize;
+use crate::audit::AuditEvent;
+use crate::config::Config;
+use crate::credentials::Credentials;
+use crate::hooks::{self, HookEvent};
 use crate::metadata::AccountsMetadata;
 use crate::paths;
+use crate::ui;
 use crate::error::SwitchError;
 
 /// Remove a saved account
@@ -9,10 +14,9 @@ pub fn remove(name: &str) -> Result<()> {
     // Load metadata
     let mut meta = AccountsMetadata::load()?;
 
-    // Check if account exists
-    if !meta.account_exists(name) {
-        return Err(SwitchError::AccountNotFound(name.to_string()).into());
-    }
+    // Resolve the account name (exact, case-insensitive or unique prefix)
+    let name = meta.resolve_account(name)?;
+    let name = name.as_str();
 
     // Check if trying to remove active account
     if meta.current_account.as_deref() == Some(name) {
@@ -21,16 +25,24 @@ pub fn remove(name: &str) -> Result<()> {
 
     // Remove credentials file
     let account_path = paths::account_credentials_path(name)?;
+    let mut event = AuditEvent::new("remove", Some(name));
     if account_path.exists() {
+        if let Some(creds) = Credentials::peek_from(&account_path) {
+            event = event.fingerprint(name, &creds);
+        }
         std::fs::remove_file(&account_path)?;
     }
 
     // Remove from metadata
     meta.remove_account(name);
     meta.save()?;
+    event.record()?;
+
+    let config = Config::load()?;
+    hooks::run(&config.hooks, HookEvent::PostRemove, &[("ACCOUNT", name.to_string())])?;
 
     println!();
-    println!("{} Account '{}' removed", "✓".green(), name.cyan());
+    ui::success(format!("Account '{}' removed", name.cyan()));
     println!();
 
     Ok(())
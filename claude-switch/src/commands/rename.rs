@@ -1,7 +1,10 @@
 use anyhow::Result;
 use colored::Colorize;
+use crate::audit::AuditEvent;
+use crate::history;
 use crate::metadata::{AccountsMetadata, validate_account_name};
 use crate::paths;
+use crate::ui;
 use crate::error::SwitchError;
 
 /// Rename an existing account
@@ -12,15 +15,15 @@ pub fn rename(old: &str, new: &str) -> Result<()> {
     // Load metadata
     let mut meta = AccountsMetadata::load()?;
 
-    // Check if old account exists
-    if !meta.account_exists(old) {
-        return Err(SwitchError::AccountNotFound(old.to_string()).into());
-    }
+    // Resolve the current name (exact, case-insensitive or unique prefix)
+    let old = meta.resolve_account(old)?;
+    let old = old.as_str();
 
     // Check if new name already exists
     if meta.account_exists(new) {
         return Err(SwitchError::AccountExists(new.to_string()).into());
     }
+    meta.check_not_alias(new)?;
 
     // Rename credentials file
     let old_path = paths::account_credentials_path(old)?;
@@ -33,14 +36,11 @@ pub fn rename(old: &str, new: &str) -> Result<()> {
     // Update metadata
     meta.rename_account(old, new)?;
     meta.save()?;
+    history::rename_account(old, new)?;
+    AuditEvent::new("rename", Some(new)).detail("from", old).record()?;
 
     println!();
-    println!(
-        "{} Renamed '{}' to '{}'",
-        "✓".green(),
-        old.dimmed(),
-        new.cyan()
-    );
+    ui::success(format!("Renamed '{}' to '{}'", old.dimmed(), new.cyan()));
     println!();
 
     Ok(())
@@ -0,0 +1,39 @@
+use anyhow::Result;
+use crate::commands::switch_account;
+use crate::config::{Config, RotationStrategy};
+use crate::history::Trigger;
+use crate::metadata::AccountsMetadata;
+use crate::select;
+use crate::error::SwitchError;
+
+/// Rotate to the next available account
+pub fn next(strategy: Option<RotationStrategy>) -> Result<()> {
+    let config = Config::load()?;
+    let meta = AccountsMetadata::load()?;
+
+    if !meta.has_accounts() {
+        return Err(SwitchError::NoAccountsSaved.into());
+    }
+
+    let strategy = strategy.unwrap_or(config.rotation.strategy);
+    let name = select::next_account(&meta, &config.rotation, strategy)
+        .ok_or(SwitchError::NoRotationCandidate)?;
+
+    switch_account(&name, Trigger::Rotation)
+}
+
+/// Go back to the previous account
+pub fn prev(strategy: Option<RotationStrategy>) -> Result<()> {
+    let config = Config::load()?;
+    let meta = AccountsMetadata::load()?;
+
+    if !meta.has_accounts() {
+        return Err(SwitchError::NoAccountsSaved.into());
+    }
+
+    let strategy = strategy.unwrap_or(config.rotation.strategy);
+    let name = select::prev_account(&meta, &config.rotation, strategy)
+        .ok_or(SwitchError::NoPreviousAccount)?;
+
+    switch_account(&name, Trigger::Rotation)
+}
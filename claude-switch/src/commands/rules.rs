@@ -0,0 +1,98 @@
+use anyhow::Result;
+use colored::Colorize;
+use crate::config::Config;
+use crate::metadata::AccountsMetadata;
+use crate::rules;
+use crate::select;
+use crate::table::Table;
+use crate::timefmt::{self, local_time};
+use crate::ui;
+
+/// List the configured switching rules
+pub fn rules_list() -> Result<()> {
+    let config = Config::load()?;
+
+    println!();
+    if config.rules.is_empty() {
+        ui::hint("No rules configured. Add a \"rules\" list to ~/.claude/accounts/config.json.");
+        println!();
+        return Ok(());
+    }
+
+    let mut table = Table::new(vec!["#", "RULE", "ACCOUNT", "WHEN", "PRIORITY"]);
+    for (i, rule) in config.rules.iter().enumerate() {
+        table.add_row(vec![
+            (i + 1).to_string(),
+            rule.label(i),
+            rule.account.cyan().to_string(),
+            rule.describe(),
+            rule.priority.to_string(),
+        ]);
+    }
+
+    ui::table("Switching Rules", &table);
+    println!();
+
+    Ok(())
+}
+
+/// Show which rules apply at a time and which account they would select
+pub fn rules_test(at: Option<&str>) -> Result<()> {
+    let config = Config::load()?;
+    let meta = AccountsMetadata::load()?;
+    let at = match at {
+        Some(at) => timefmt::parse_time(at)?,
+        None => chrono::Utc::now().timestamp_millis(),
+    };
+
+    println!();
+    if config.rules.is_empty() {
+        ui::hint("No rules configured. Add a \"rules\" list to ~/.claude/accounts/config.json.");
+        println!();
+        return Ok(());
+    }
+
+    let selected = rules::select(&config.rules, &meta, at)?;
+
+    let mut table = Table::new(vec!["", "#", "RULE", "ACCOUNT", "WHEN", "PRIORITY", "RESULT"]);
+    for (i, rule) in config.rules.iter().enumerate() {
+        let (mark, result) = if selected.as_ref().is_some_and(|(index, _)| *index == i) {
+            (ui::ok_mark().to_string(), "selected".green().to_string())
+        } else if !rule.matches(at)? {
+            (String::new(), "no match".dimmed().to_string())
+        } else {
+            match meta.resolve_account(&rule.account) {
+                Err(_) => (ui::warn_mark().to_string(), "account not found".yellow().to_string()),
+                Ok(name) if !select::is_available(&meta, &name) => {
+                    (ui::warn_mark().to_string(), "account unavailable".yellow().to_string())
+                }
+                Ok(_) => (String::new(), "outranked".to_string()),
+            }
+        };
+
+        table.add_row(vec![
+            mark,
+            (i + 1).to_string(),
+            rule.label(i),
+            rule.account.cyan().to_string(),
+            rule.describe(),
+            rule.priority.to_string(),
+            result,
+        ]);
+    }
+
+    ui::table(&format!("Rules at {}", local_time(at)), &table);
+    println!();
+
+    match selected {
+        Some((i, account)) => ui::success(format!(
+            "Rule '{}' selects '{}'",
+            config.rules[i].label(i),
+            account.cyan()
+        )),
+        None => ui::info("No rule applies; 'auto' would leave the active account alone"),
+    }
+    println!();
+
+    Ok(())
+}
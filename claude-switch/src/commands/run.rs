@@ -0,0 +1,248 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::{Child, Command};
+use std::time::{Duration, Instant};
+use crate::commands::switch_account;
+use crate::config::Config;
+use crate::error::SwitchError;
+use crate::history::Trigger;
+use crate::metadata::AccountsMetadata;
+use crate::paths;
+use crate::select;
+use crate::timefmt::{clock_time, countdown};
+use crate::transcripts::{self, LimitEvent};
+use crate::ui;
+
+/// How often the transcripts are checked for a usage limit
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long Claude Code gets to exit after SIGTERM before it is killed
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Exit code when the child could not report one (killed by a signal)
+const EXIT_SIGNALLED: i32 = 128;
+
+/// Run Claude Code as a child, rotating to the next available account and
+/// resuming the session whenever it hits a usage limit.
+///
+/// Returns the exit code of the last child.
+pub fn run(command: &[String], max_rotations: Option<u32>) -> Result<i32> {
+    let Some((program, args)) = command.split_first() else {
+        return Err(SwitchError::NoCommand.into());
+    };
+    let config = Config::load()?;
+    let max_rotations = max_rotations.unwrap_or(config.run.max_rotations);
+
+    ignore_interrupts();
+
+    let cwd = std::env::current_dir()?;
+    let mut args = args.to_vec();
+    let mut rotations = 0;
+
+    loop {
+        let mut watcher = TranscriptWatcher::new(&cwd)?;
+        let mut child = Command::new(program).args(&args).spawn()?;
+
+        let Some(limit) = supervise(&mut child, &mut watcher)? else {
+            let status = child.wait()?;
+            return Ok(status.code().unwrap_or(EXIT_SIGNALLED));
+        };
+
+        stop(&mut child)?;
+
+        let mut meta = AccountsMetadata::load()?;
+        let limited = meta.current_account.clone();
+        println!();
+        if let Some(info) = limited.as_deref().and_then(|name| meta.accounts.get_mut(name)) {
+            info.limited_until = Some(limit.resets_at);
+            meta.save()?;
+        }
+        ui::warning(format!(
+            "'{}' hit its usage limit; it resets at {} (in {})",
+            limited.as_deref().unwrap_or("unknown").cyan(),
+            clock_time(limit.resets_at),
+            countdown(limit.resets_at - chrono::Utc::now().timestamp_millis())
+        ));
+
+        if rotations >= max_rotations {
+            ui::warning(format!(
+                "Reached the limit of {} rotation(s); not switching again",
+                max_rotations
+            ));
+            println!();
+            return Ok(1);
+        }
+
+        let next = select::next_account(&meta, &config.rotation, config.rotation.strategy)
+            .ok_or(SwitchError::NoRotationCandidate)?;
+        switch_account(&next, Trigger::Rotation)?;
+        rotations += 1;
+
+        args = resume_args(&args, limit.session_id.or(watcher.session_id));
+        ui::info(format!(
+            "Resuming on '{}' (rotation {} of {})",
+            next.cyan(),
+            rotations,
+            max_rotations
+        ));
+        println!();
+    }
+}
+
+/// Wait for the child to exit or hit a usage limit, whichever comes first
+fn supervise(child: &mut Child, watcher: &mut TranscriptWatcher) -> Result<Option<LimitEvent>> {
+    let mut next_poll = Instant::now() + POLL_INTERVAL;
+    loop {
+        if child.try_wait()?.is_some() {
+            // Catch a limit written just before exiting
+            return watcher.poll();
+        }
+        if Instant::now() >= next_poll {
+            if let Some(limit) = watcher.poll()? {
+                return Ok(Some(limit));
+            }
+            next_poll = Instant::now() + POLL_INTERVAL;
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
+
+/// Ask the child to exit, killing it if it doesn't within the timeout
+fn stop(child: &mut Child) -> Result<()> {
+    if child.try_wait()?.is_some() {
+        return Ok(());
+    }
+
+    #[cfg(unix)]
+    {
+        // SAFETY: kill has no memory-safety preconditions
+        unsafe {
+            libc::kill(child.id() as i32, libc::SIGTERM);
+        }
+        let deadline = Instant::now() + STOP_TIMEOUT;
+        while Instant::now() < deadline {
+            if child.try_wait()?.is_some() {
+                return Ok(());
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    child.kill()?;
+    child.wait()?;
+    Ok(())
+}
+
+/// Drop any resume flags from the original arguments and resume the
+/// session that hit the limit (or the latest one in this directory)
+fn resume_args(args: &[String], session_id: Option<String>) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-c" | "--continue" => {}
+            "-r" | "--resume" => {
+                if iter.peek().is_some_and(|next| !next.starts_with('-')) {
+                    iter.next();
+                }
+            }
+            a if a.starts_with("--resume=") => {}
+            _ => out.push(arg.clone()),
+        }
+    }
+
+    match session_id {
+        Some(id) => out.extend(["--resume".to_string(), id]),
+        None => out.push("--continue".to_string()),
+    }
+    out
+}
+
+/// Follows transcripts written after it was created
+struct TranscriptWatcher {
+    started_at: i64,
+    dir: PathBuf,
+    offsets: HashMap<PathBuf, u64>,
+    /// Session seen most recently in new transcript lines
+    session_id: Option<String>,
+}
+
+impl TranscriptWatcher {
+    /// Start watching the sessions of `cwd`, skipping what is already on
+    /// disk. If Claude Code hasn't created this directory's project folder
+    /// yet, polling finds nothing until it appears.
+    fn new(cwd: &std::path::Path) -> Result<Self> {
+        let dir = transcripts::project_dir(&paths::projects_dir()?, cwd);
+
+        let offsets = transcripts::transcript_files(&dir)?
+            .into_iter()
+            .filter_map(|f| std::fs::metadata(&f).ok().map(|m| (f, m.len())))
+            .collect();
+
+        Ok(Self {
+            started_at: chrono::Utc::now().timestamp_millis(),
+            dir,
+            offsets,
+            session_id: None,
+        })
+    }
+
+    /// Read new transcript lines, returning the first usage limit found
+    fn poll(&mut self) -> Result<Option<LimitEvent>> {
+        let now = chrono::Utc::now().timestamp_millis();
+        for file in transcripts::transcript_files(&self.dir)? {
+            // Other sessions in this directory may be idle; only files
+            // written since the child started can belong to it
+            if !modified_since(&file, self.started_at) {
+                continue;
+            }
+            let offset = self.offsets.get(&file).copied().unwrap_or(0);
+            let Ok((lines, new_offset)) = transcripts::read_new_lines(&file, offset) else {
+                continue;
+            };
+            self.offsets.insert(file, new_offset);
+
+            for line in lines {
+                let Some(entry) = transcripts::parse_line(&line) else {
+                    continue;
+                };
+                let recent = entry.timestamp_ms().is_some_and(|at| at >= self.started_at);
+                if recent && entry.session_id.is_some() {
+                    self.session_id = entry.session_id.clone();
+                }
+                let Some(event) = transcripts::limit_event(&entry) else {
+                    continue;
+                };
+                if event.at >= self.started_at && event.resets_at > now {
+                    return Ok(Some(event));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Whether a file was last written at or after `since` (ms). Some file
+/// systems only keep whole seconds, so `since` is rounded down to one.
+fn modified_since(path: &std::path::Path, since: i64) -> bool {
+    let since = since - since.rem_euclid(1000);
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(|t| chrono::DateTime::<chrono::Utc>::from(t).timestamp_millis() >= since)
+        .unwrap_or(false)
+}
+
+/// Let Ctrl-C reach Claude Code without ending the supervisor. A handler
+/// is installed rather than SIG_IGN, which the child would inherit.
+fn ignore_interrupts() {
+    #[cfg(unix)]
+    {
+        extern "C" fn noop(_: libc::c_int) {}
+        // SAFETY: the handler does nothing, so it is async-signal-safe
+        unsafe {
+            libc::signal(libc::SIGINT, noop as *const () as libc::sighandler_t);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::history;
+use crate::metadata::AccountsMetadata;
+use crate::paths;
+use crate::timefmt::{local_time, relative_time};
+use crate::transcripts;
+use crate::ui;
+
+/// How far each transcript has been scanned, so runs only read new lines
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ScanState {
+    /// Byte offset per transcript path
+    offsets: BTreeMap<String, u64>,
+}
+
+impl ScanState {
+    fn load() -> Result<Self> {
+        let path = paths::scan_state_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&contents).unwrap_or_default())
+    }
+
+    fn save(&self) -> Result<()> {
+        paths::ensure_accounts_dir()?;
+        std::fs::write(paths::scan_state_path()?, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+/// A cooldown recorded from a transcript limit event
+#[derive(Debug, Clone)]
+pub struct AppliedLimit {
+    pub account: String,
+    pub resets_at: i64,
+    pub session_id: Option<String>,
+}
+
+/// Scan transcripts for new usage-limit events and record cooldowns on
+/// the accounts that were active when they happened.
+///
+/// Events whose reset time has already passed are skipped. With `rescan`,
+/// every transcript is read from the start.
+pub fn scan(rescan: bool) -> Result<Vec<AppliedLimit>> {
+    let mut state = if rescan { ScanState::default() } else { ScanState::load()? };
+    let meta = AccountsMetadata::load()?;
+    let records = history::load()?;
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let mut found: Vec<AppliedLimit> = Vec::new();
+
+    for file in transcripts::transcript_files(&paths::projects_dir()?)? {
+        let key = file.display().to_string();
+        let offset = state.offsets.get(&key).copied().unwrap_or(0);
+        let Ok((lines, new_offset)) = transcripts::read_new_lines(&file, offset) else {
+            continue;
+        };
+        state.offsets.insert(key, new_offset);
+
+        for line in lines {
+            // Cheap pre-filter before parsing JSON
+            if !line.contains("limit") {
+                continue;
+            }
+            let Some(event) = transcripts::parse_line(&line).and_then(|e| transcripts::limit_event(&e))
+            else {
+                continue;
+            };
+            if event.resets_at <= now {
+                continue;
+            }
+
+            let current = meta.current_account.as_deref();
+            let Some(account) = history::account_at(&records, event.at, current).map(String::from)
+            else {
+                continue;
+            };
+            found.push(AppliedLimit {
+                account,
+                resets_at: event.resets_at,
+                session_id: event.session_id,
+            });
+        }
+    }
+
+    // Drop offsets for transcripts that no longer exist
+    state.offsets.retain(|path, _| std::path::Path::new(path).exists());
+
+    // The scan can take a while; apply the cooldowns to fresh metadata so
+    // changes made in the meantime (by the daemon or `limited`) survive
+    let mut applied: Vec<AppliedLimit> = Vec::new();
+    if !found.is_empty() {
+        let mut meta = AccountsMetadata::load()?;
+        for limit in found {
+            let Some(info) = meta.accounts.get_mut(&limit.account) else {
+                continue;
+            };
+            if info.limited_until.is_none_or(|until| until < limit.resets_at) {
+                info.limited_until = Some(limit.resets_at);
+                applied.push(limit);
+            }
+        }
+        if !applied.is_empty() {
+            meta.save()?;
+        }
+    }
+    state.save()?;
+
+    Ok(applied)
+}
+
+/// Scan transcripts once and report the cooldowns recorded
+pub fn scan_limits(rescan: bool) -> Result<()> {
+    let applied = scan(rescan)?;
+
+    println!();
+    if applied.is_empty() {
+        ui::hint("No new usage-limit events.");
+    } else {
+        for limit in &applied {
+            ui::success(describe(limit));
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// One-line description of a recorded cooldown
+pub fn describe(limit: &AppliedLimit) -> String {
+    let session = limit
+        .session_id
+        .as_deref()
+        .map(|s| format!(" (session {})", &s[..s.len().min(8)]))
+        .unwrap_or_default();
+
+    format!(
+        "'{}' hit its usage limit; limited until {} ({}){}",
+        limit.account.cyan(),
+        local_time(limit.resets_at),
+        relative_time(limit.resets_at),
+        session.dimmed()
+    )
+}
@@ -0,0 +1,176 @@
+use anyhow::Result;
+use colored::Colorize;
+use crate::credentials::{token_fingerprint, Credentials, TokenStatus};
+use crate::metadata::{AccountsMetadata, validate_account_name};
+use crate::paths;
+use crate::timefmt::relative_and_local;
+use crate::ui::{self, field};
+use crate::error::SwitchError;
+
+/// Scope Claude Code needs to make inference requests
+const INFERENCE_SCOPE: &str = "user:inference";
+
+/// Show everything known about one account (default: the active one)
+pub fn show(name: Option<&str>) -> Result<()> {
+    let meta = AccountsMetadata::load()?;
+
+    let name = match name {
+        Some(name) => resolve_name(&meta, name)?,
+        None => meta
+            .current_account
+            .clone()
+            .ok_or(SwitchError::NoActiveAccount)?,
+    };
+
+    let info = meta.get_account(&name);
+    let account_path = paths::account_credentials_path(&name)?;
+    let file_exists = account_path.exists();
+
+    if info.is_none() && !file_exists {
+        return Err(SwitchError::AccountNotFound(name).into());
+    }
+
+    let creds = if file_exists {
+        Some(Credentials::load_from(&account_path)?)
+    } else {
+        None
+    };
+
+    let mut problems: Vec<String> = Vec::new();
+    let is_current = meta.current_account.as_deref() == Some(name.as_str());
+
+    println!();
+    if is_current {
+        println!("{} {}", name.cyan().bold(), format!("{} active", ui::glyphs().active).green());
+    } else {
+        println!("{}", name.cyan().bold());
+    }
+    println!("{}", ui::rule(50));
+
+    if let Some(creds) = &creds {
+        let oauth = &creds.claude_ai_oauth;
+        let status = TokenStatus::from_expires_at(oauth.expires_at);
+
+        field("Subscription:", &oauth.subscription_type);
+        field("Rate limit tier:", &oauth.rate_limit_tier);
+        field("Token status:", status.display());
+        field("Expires:", relative_and_local(oauth.expires_at));
+        field("Scopes:", oauth.scopes.join(", "));
+        field("MCP OAuth:", mcp_summary(&creds.mcp_oauth));
+        field("Access token:", token_fingerprint(&oauth.access_token));
+        field("Refresh token:", token_fingerprint(&oauth.refresh_token));
+
+        if !oauth.scopes.iter().any(|s| s == INFERENCE_SCOPE) {
+            problems.push(format!("Missing '{}' scope", INFERENCE_SCOPE));
+        }
+        if status == TokenStatus::Expired {
+            problems.push("Token is expired".to_string());
+        }
+    } else {
+        problems.push("Credentials file is missing".to_string());
+    }
+
+    field("File:", file_summary(&account_path, &mut problems));
+
+    if let Some(info) = info {
+        field("Added:", relative_and_local(info.added_at));
+        field("Last used:", relative_and_local(info.last_used_at));
+        if let Some(until) = info.limited_until.filter(|_| info.cooldown_remaining().is_some()) {
+            field("Limited until:", relative_and_local(until).yellow());
+            problems.push("Account is cooling down after a rate limit".to_string());
+        }
+        let aliases = meta.aliases_for(&name);
+        if !aliases.is_empty() {
+            field("Aliases:", aliases.join(", "));
+        }
+        if !info.tags.is_empty() {
+            let tags: Vec<&str> = info.tags.iter().map(String::as_str).collect();
+            field("Tags:", tags.join(", "));
+        }
+        if let Some(note) = info.note() {
+            let mut lines = note.lines();
+            field("Notes:", lines.next().unwrap_or(""));
+            for line in lines {
+                field("", line);
+            }
+        }
+
+        if let Some(creds) = &creds {
+            if info.subscription_type != creds.subscription_type() {
+                problems.push(format!(
+                    "Metadata says '{}' but credentials say '{}'",
+                    info.subscription_type,
+                    creds.subscription_type()
+                ));
+            }
+            if info.token_expires_at != creds.expires_at() {
+                problems.push("Metadata token expiry differs from credentials file".to_string());
+            }
+        }
+    } else {
+        problems.push("No metadata entry for this credentials file".to_string());
+    }
+
+    println!();
+    if !problems.is_empty() {
+        println!("{}", "Problems".bold());
+        for problem in &problems {
+            println!("  {} {}", ui::warn_mark(), problem);
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Resolve through metadata, but still allow inspecting a credentials
+/// file that has no metadata entry. The fallback only takes valid account
+/// names, so the input can't reach files outside the store.
+fn resolve_name(meta: &AccountsMetadata, input: &str) -> Result<String> {
+    match meta.resolve_account(input) {
+        Ok(name) => Ok(name),
+        Err(_)
+            if validate_account_name(input).is_ok()
+                && paths::account_credentials_path(input)?.exists() =>
+        {
+            Ok(input.to_string())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn mcp_summary(mcp: &Option<serde_json::Value>) -> String {
+    match mcp {
+        Some(serde_json::Value::Object(entries)) if !entries.is_empty() => {
+            let mut names: Vec<_> = entries.keys().map(|k| k.as_str()).collect();
+            names.sort();
+            format!("{} entries ({})", names.len(), names.join(", "))
+        }
+        Some(serde_json::Value::Object(_)) | Some(serde_json::Value::Null) | None => {
+            "none".to_string()
+        }
+        Some(_) => "present".to_string(),
+    }
+}
+
+/// Describe the credentials file path and permissions, noting problems
+fn file_summary(path: &std::path::Path, problems: &mut Vec<String>) -> String {
+    let display = path.display().to_string();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(file_meta) = std::fs::metadata(path) {
+            let mode = file_meta.permissions().mode() & 0o777;
+            if mode & 0o077 != 0 {
+                problems.push(format!("Credentials file is accessible by others ({:04o})", mode));
+            }
+            return format!("{} ({:04o})", display, mode);
+        }
+    }
+
+    #[cfg(not(unix))]
+    let _ = problems;
+
+    display
+}
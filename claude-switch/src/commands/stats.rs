@@ -0,0 +1,202 @@
+use anyhow::Result;
+use chrono::{Datelike, TimeZone};
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use crate::history;
+use crate::table::Table;
+use crate::timefmt::{self, countdown, local_time};
+use crate::ui;
+
+/// Period `stats` breaks active time down by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatsPeriod {
+    Day,
+    Week,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountStats {
+    account: String,
+    active_ms: i64,
+    share: f64,
+    sessions: usize,
+    average_session_ms: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PeriodStats {
+    period: String,
+    account: String,
+    active_ms: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Report {
+    since: Option<i64>,
+    until: i64,
+    switches: usize,
+    most_used: Option<String>,
+    accounts: Vec<AccountStats>,
+    periods: Vec<PeriodStats>,
+}
+
+/// Report how long each account has been active, from the switch history
+pub fn stats(since: Option<&str>, by: StatsPeriod, json: bool) -> Result<()> {
+    let since = since.map(timefmt::parse_since).transpose()?;
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let records = history::load()?;
+    let switches = records
+        .iter()
+        .filter(|r| since.is_none_or(|since| r.at >= since))
+        .count();
+
+    // Clip sessions to the reporting period
+    let sessions: Vec<history::Session> = history::sessions(&records, now)
+        .into_iter()
+        .filter_map(|mut s| {
+            if let Some(since) = since {
+                if s.end <= since {
+                    return None;
+                }
+                s.start = s.start.max(since);
+            }
+            Some(s)
+        })
+        .collect();
+
+    let mut per_account: BTreeMap<&str, (i64, usize)> = BTreeMap::new();
+    let mut per_period: BTreeMap<(String, &str), i64> = BTreeMap::new();
+    for session in &sessions {
+        let entry = per_account.entry(&session.account).or_default();
+        entry.0 += session.end - session.start;
+        entry.1 += 1;
+
+        for (period, ms) in split_by_period(session.start, session.end, by) {
+            *per_period.entry((period, session.account.as_str())).or_default() += ms;
+        }
+    }
+
+    let total: i64 = per_account.values().map(|(ms, _)| ms).sum();
+    let mut accounts: Vec<AccountStats> = per_account
+        .into_iter()
+        .map(|(account, (active_ms, sessions))| AccountStats {
+            account: account.to_string(),
+            active_ms,
+            share: if total > 0 {
+                (active_ms as f64 * 1000.0 / total as f64).round() / 10.0
+            } else {
+                0.0
+            },
+            sessions,
+            average_session_ms: active_ms / sessions as i64,
+        })
+        .collect();
+    accounts.sort_by(|a, b| b.active_ms.cmp(&a.active_ms).then_with(|| a.account.cmp(&b.account)));
+
+    let report = Report {
+        since,
+        until: now,
+        switches,
+        most_used: accounts.first().map(|a| a.account.clone()),
+        accounts,
+        periods: per_period
+            .into_iter()
+            .map(|((period, account), active_ms)| PeriodStats {
+                period,
+                account: account.to_string(),
+                active_ms,
+            })
+            .collect(),
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!();
+    if report.accounts.is_empty() {
+        ui::hint("No account switches recorded for this period.");
+        println!();
+        return Ok(());
+    }
+
+    let mut table = Table::new(vec!["ACCOUNT", "ACTIVE", "SHARE", "SESSIONS", "AVG SESSION"]);
+    for a in &report.accounts {
+        table.add_row(vec![
+            a.account.cyan().to_string(),
+            countdown(a.active_ms),
+            format!("{:.0}%", a.share),
+            a.sessions.to_string(),
+            countdown(a.average_session_ms),
+        ]);
+    }
+    ui::table("Active Time", &table);
+    println!();
+
+    let mut table = Table::new(vec![
+        match by {
+            StatsPeriod::Day => "DAY",
+            StatsPeriod::Week => "WEEK",
+        },
+        "ACCOUNT",
+        "ACTIVE",
+    ]);
+    for p in &report.periods {
+        table.add_row(vec![p.period.clone(), p.account.cyan().to_string(), countdown(p.active_ms)]);
+    }
+    ui::table("Per Period", &table);
+    println!();
+
+    if let Some(top) = report.accounts.first() {
+        ui::field("Most used:", format!("{} ({:.0}% of active time)", top.account.cyan(), top.share));
+    }
+    ui::field("Switches:", report.switches);
+    if let Some(since) = since {
+        ui::field("Since:", local_time(since));
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Split `[start, end)` at local day or ISO week boundaries, returning the
+/// time spent in each period
+fn split_by_period(start: i64, end: i64, by: StatsPeriod) -> Vec<(String, i64)> {
+    let mut parts = Vec::new();
+    let mut cursor = start;
+
+    while cursor < end {
+        let Some(local) = chrono::Local.timestamp_millis_opt(cursor).single() else {
+            break;
+        };
+        let date = local.date_naive();
+        let (label, next_start) = match by {
+            StatsPeriod::Day => (date.format("%Y-%m-%d").to_string(), date.succ_opt()),
+            StatsPeriod::Week => {
+                let week = date.iso_week();
+                let monday = date - chrono::Days::new(u64::from(date.weekday().num_days_from_monday()));
+                (
+                    format!("{}-W{:02}", week.year(), week.week()),
+                    monday.checked_add_days(chrono::Days::new(7)),
+                )
+            }
+        };
+
+        let boundary = next_start
+            .and_then(|d| chrono::Local.from_local_datetime(&d.and_time(chrono::NaiveTime::MIN)).earliest())
+            .map_or(end, |t| t.timestamp_millis())
+            .min(end);
+
+        parts.push((label, boundary - cursor));
+        cursor = boundary;
+    }
+
+    parts
+}
@@ -0,0 +1,167 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use crate::credentials::{Credentials, TokenStatus};
+use crate::metadata::AccountsMetadata;
+use crate::paths;
+
+/// Exit codes reported by `status`, so prompts can react to token health
+const EXIT_VALID: i32 = 0;
+const EXIT_WARNING: i32 = 2;
+const EXIT_EXPIRED: i32 = 3;
+
+/// The few account fields a status template can show
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct AccountSummary {
+    pub name: Option<String>,
+
+    #[serde(rename = "subscriptionType")]
+    pub subscription_type: String,
+
+    #[serde(rename = "expiresAt")]
+    pub expires_at: i64,
+}
+
+/// Cached account summary, valid while the source files keep their mtimes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StatusCache {
+    #[serde(rename = "credentialsMtime")]
+    credentials_mtime: u64,
+
+    #[serde(rename = "metadataMtime")]
+    metadata_mtime: Option<u64>,
+
+    #[serde(flatten)]
+    summary: AccountSummary,
+}
+
+/// Print a one-line account summary for shell prompts and status bars.
+///
+/// Deliberately avoids colour output and reads as little as possible.
+/// Returns the process exit code reflecting token health.
+pub fn status(format: &str, use_cache: bool) -> Result<i32> {
+    let summary = if use_cache { load_cached()? } else { load_fresh()? };
+
+    println!("{}", render(format, &summary));
+
+    Ok(match TokenStatus::from_expires_at(summary.expires_at) {
+        TokenStatus::Valid { .. } => EXIT_VALID,
+        TokenStatus::Warning { .. } => EXIT_WARNING,
+        TokenStatus::Expired => EXIT_EXPIRED,
+    })
+}
+
+/// Read the summary straight from the credentials and metadata files
+fn load_fresh() -> Result<AccountSummary> {
+    let creds = Credentials::load_active()?;
+    let meta = AccountsMetadata::load()?;
+
+    Ok(AccountSummary {
+        name: meta.current_account,
+        subscription_type: creds.subscription_type().to_string(),
+        expires_at: creds.expires_at(),
+    })
+}
+
+/// Use the cache if both source files are unchanged, refreshing it otherwise
+fn load_cached() -> Result<AccountSummary> {
+    let creds_path = paths::credentials_path()?;
+    let meta_path = paths::metadata_path()?;
+    let cache_path = paths::status_cache_path()?;
+
+    let credentials_mtime = mtime_nanos(&creds_path);
+    let metadata_mtime = mtime_nanos(&meta_path);
+
+    if let Some(credentials_mtime) = credentials_mtime {
+        let cached = std::fs::read_to_string(&cache_path)
+            .ok()
+            .and_then(|s| serde_json::from_str::<StatusCache>(&s).ok());
+        if let Some(cached) = cached {
+            if cached.credentials_mtime == credentials_mtime
+                && cached.metadata_mtime == metadata_mtime
+            {
+                return Ok(cached.summary);
+            }
+        }
+    }
+
+    let cache = StatusCache {
+        credentials_mtime: credentials_mtime.unwrap_or(0),
+        metadata_mtime,
+        summary: load_fresh()?,
+    };
+
+    // The cache is best-effort; never create the accounts dir just for it
+    if cache_path.parent().is_some_and(|dir| dir.exists()) {
+        if let Ok(contents) = serde_json::to_string(&cache) {
+            let _ = std::fs::write(&cache_path, contents);
+        }
+    }
+
+    Ok(cache.summary)
+}
+
+/// Modification time of a file in nanoseconds since the epoch
+fn mtime_nanos(path: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let nanos = modified.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+    u64::try_from(nanos).ok()
+}
+
+/// Expand `{placeholder}`s in the template; unknown ones are kept verbatim
+pub(crate) fn render(format: &str, summary: &AccountSummary) -> String {
+    let token = TokenStatus::from_expires_at(summary.expires_at);
+    let mut out = String::with_capacity(format.len() + 16);
+    let mut rest = format;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+
+        let key = &after[..end];
+        match placeholder(key, summary, &token) {
+            Some(value) => out.push_str(&value),
+            None => {
+                out.push('{');
+                out.push_str(key);
+                out.push('}');
+            }
+        }
+        rest = &after[end + 1..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn placeholder(key: &str, summary: &AccountSummary, token: &TokenStatus) -> Option<String> {
+    let value = match key {
+        "name" => summary.name.clone().unwrap_or_else(|| "unknown".to_string()),
+        "type" => summary.subscription_type.clone(),
+        "expires_in" => expires_in(summary.expires_at),
+        "status" => token.label().to_string(),
+        "status_icon" => token.glyph().to_string(),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Compact time until expiry, e.g. `4d`, `12h`, `35m` or `expired`
+fn expires_in(expires_at: i64) -> String {
+    let minutes = (expires_at - chrono::Utc::now().timestamp_millis()) / (1000 * 60);
+
+    if minutes < 0 {
+        "expired".to_string()
+    } else if minutes < 60 {
+        format!("{}m", minutes)
+    } else if minutes < 60 * 24 {
+        format!("{}h", minutes / 60)
+    } else {
+        format!("{}d", minutes / (60 * 24))
+    }
+}
@@ -0,0 +1,95 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use crate::commands::status::{render, AccountSummary};
+use crate::credentials::Credentials;
+use crate::metadata::AccountsMetadata;
+use crate::paths;
+
+/// The part of Claude Code's status line session JSON we care about
+#[derive(Debug, Default, Deserialize)]
+struct SessionInput {
+    #[serde(default)]
+    transcript_path: Option<PathBuf>,
+}
+
+/// Print a status line segment for the Claude Code session described on stdin
+pub fn statusline(format: &str) -> Result<()> {
+    let session = read_session();
+    let config_dir = session_config_dir(&session)?;
+
+    let creds = Credentials::load_from(&config_dir.join(".credentials.json"))?;
+    let meta = AccountsMetadata::load()?;
+
+    let name = if config_dir == paths::claude_dir()? {
+        meta.current_account.clone()
+    } else {
+        find_saved_account(&meta, &creds).or_else(|| account_from_dir_name(&meta, &config_dir))
+    };
+
+    let summary = AccountSummary {
+        name,
+        subscription_type: creds.subscription_type().to_string(),
+        expires_at: creds.expires_at(),
+    };
+    println!("{}", render(format, &summary));
+
+    Ok(())
+}
+
+/// Parse the session JSON, tolerating an interactive or empty stdin
+fn read_session() -> SessionInput {
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return SessionInput::default();
+    }
+
+    let mut input = String::new();
+    if stdin.lock().read_to_string(&mut input).is_err() {
+        return SessionInput::default();
+    }
+    serde_json::from_str(&input).unwrap_or_default()
+}
+
+/// Work out which Claude config dir the session runs under.
+///
+/// `CLAUDE_CONFIG_DIR` is inherited from the session; otherwise the
+/// transcript path (`<config>/projects/<project>/<session>.jsonl`) tells us.
+fn session_config_dir(session: &SessionInput) -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os("CLAUDE_CONFIG_DIR").filter(|d| !d.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    let from_transcript = session
+        .transcript_path
+        .as_deref()
+        .and_then(|p| p.ancestors().nth(3))
+        .filter(|dir| dir.join("projects").is_dir());
+
+    match from_transcript {
+        Some(dir) => Ok(dir.to_path_buf()),
+        None => Ok(paths::claude_dir()?),
+    }
+}
+
+/// Find the saved account whose stored credentials match these
+fn find_saved_account(meta: &AccountsMetadata, creds: &Credentials) -> Option<String> {
+    let mut names: Vec<_> = meta.accounts.keys().collect();
+    names.sort();
+
+    names.into_iter().find_map(|name| {
+        let path = paths::account_credentials_path(name).ok()?;
+        let saved = Credentials::peek_from(&path)?;
+        saved.same_login(creds).then(|| name.clone())
+    })
+}
+
+/// Fall back to per-account dir naming such as `~/.claude-work`
+fn account_from_dir_name(meta: &AccountsMetadata, dir: &Path) -> Option<String> {
+    let dir_name = dir.file_name()?.to_str()?;
+    let name = dir_name
+        .strip_prefix(".claude-")
+        .or_else(|| dir_name.strip_prefix("claude-"))?;
+    meta.account_exists(name).then(|| name.to_string())
+}
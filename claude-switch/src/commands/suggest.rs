@@ -0,0 +1,216 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::collections::HashMap;
+use crate::commands::use_account::{use_account, RestartMode};
+use crate::credentials::TokenStatus;
+use crate::error::SwitchError;
+use crate::metadata::AccountsMetadata;
+use crate::paths;
+use crate::table::Table;
+use crate::timefmt::{clock_time, countdown};
+use crate::ui;
+use crate::usage;
+
+/// How far back to look for usage windows when estimating each
+/// subscription tier's window budget
+const BUDGET_LOOKBACK_MS: i64 = 14 * 24 * 60 * 60 * 1000;
+
+/// Whether an account can take work right now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Readiness {
+    Ready,
+    Limited,
+    Expired,
+    Missing,
+}
+
+/// One ranked account with the reasons behind its place
+#[derive(Debug)]
+struct Suggestion {
+    name: String,
+    readiness: Readiness,
+    /// Share of the current 5-hour window already used, if known
+    used_pct: Option<u64>,
+    expiring: bool,
+    last_used_at: i64,
+    limited_until: Option<i64>,
+    reasons: Vec<String>,
+}
+
+impl Suggestion {
+    /// Rough percentage of capacity left right now
+    fn headroom(&self) -> String {
+        match self.readiness {
+            Readiness::Ready => match self.used_pct {
+                Some(pct) => format!("{}%", 100 - pct),
+                None => "?".to_string(),
+            },
+            _ => "0%".to_string(),
+        }
+    }
+
+    fn rank_key(&self) -> (Readiness, i64, u64, bool, i64) {
+        (
+            self.readiness,
+            // Limited accounts: the one that frees up first ranks higher
+            self.limited_until.unwrap_or(0),
+            self.used_pct.unwrap_or(50),
+            self.expiring,
+            self.last_used_at,
+        )
+    }
+}
+
+/// Rank saved accounts by how much headroom they likely have right now
+pub fn suggest(switch: bool) -> Result<()> {
+    let meta = AccountsMetadata::load()?;
+    if !meta.has_accounts() {
+        return Err(SwitchError::NoAccountsSaved.into());
+    }
+
+    let suggestions = rank(&meta)?;
+
+    if switch {
+        let top = suggestions
+            .first()
+            .filter(|s| s.readiness == Readiness::Ready)
+            .ok_or(SwitchError::NoAvailableAccount)?;
+        return use_account(&top.name, RestartMode::Warn);
+    }
+
+    let current = meta.current_account.as_deref();
+    let mut table = Table::new(vec!["", "#", "ACCOUNT", "HEADROOM", "WHY"]);
+    for (i, s) in suggestions.iter().enumerate() {
+        let marker = if Some(s.name.as_str()) == current {
+            ui::active_mark().to_string()
+        } else {
+            String::new()
+        };
+        let headroom = match s.readiness {
+            Readiness::Ready => s.headroom().green().to_string(),
+            Readiness::Limited => s.headroom().yellow().to_string(),
+            _ => s.headroom().red().to_string(),
+        };
+        table.add_row(vec![
+            marker,
+            (i + 1).to_string(),
+            s.name.cyan().to_string(),
+            headroom,
+            s.reasons.join(", "),
+        ]);
+    }
+
+    println!();
+    ui::table("Suggested Accounts", &table);
+    println!();
+
+    match suggestions.first() {
+        Some(top) if top.readiness == Readiness::Ready && Some(top.name.as_str()) != current => {
+            ui::hint(format!(
+                "  Run 'claude-switch suggest --use' to switch to '{}'",
+                top.name
+            ));
+        }
+        Some(top) if top.readiness == Readiness::Ready => {
+            ui::hint("  The active account is the best pick right now");
+        }
+        _ => ui::hint("  No account is available right now"),
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Score every saved account, best first.
+///
+/// Window usage is measured against the largest window total seen for the
+/// same subscription tier in the last two weeks, which is the best local
+/// estimate of where the limit sits.
+fn rank(meta: &AccountsMetadata) -> Result<Vec<Suggestion>> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let records = usage::load_records(Some(now - BUDGET_LOOKBACK_MS))?;
+    let windows = usage::windows(&records);
+
+    let tier = |name: &str| {
+        meta.get_account(name)
+            .map_or("unknown", |info| info.subscription_type.as_str())
+    };
+
+    let mut budgets: HashMap<&str, u64> = HashMap::new();
+    for window in &windows {
+        let budget = budgets.entry(tier(&window.account)).or_default();
+        *budget = (*budget).max(window.tokens.total());
+    }
+
+    let mut suggestions: Vec<Suggestion> = meta
+        .accounts
+        .iter()
+        .map(|(name, info)| {
+            let mut reasons = Vec::new();
+            let status = TokenStatus::from_expires_at(info.token_expires_at);
+            let has_credentials = paths::account_credentials_path(name).is_ok_and(|p| p.exists());
+            let cooldown = info.cooldown_remaining();
+
+            let readiness = if !has_credentials {
+                reasons.push("saved credentials missing".to_string());
+                Readiness::Missing
+            } else if status == TokenStatus::Expired {
+                reasons.push("token expired".to_string());
+                Readiness::Expired
+            } else if let Some(remaining) = cooldown {
+                reasons.push(format!(
+                    "limited until {} ({})",
+                    clock_time(now + remaining),
+                    countdown(remaining)
+                ));
+                Readiness::Limited
+            } else {
+                Readiness::Ready
+            };
+
+            let open = windows
+                .iter()
+                .rev()
+                .find(|w| &w.account == name && w.end > now && w.start <= now);
+
+            let used_pct = match open {
+                None => {
+                    if readiness == Readiness::Ready {
+                        reasons.push("no usage in the last 5 hours".to_string());
+                    }
+                    Some(0)
+                }
+                Some(window) => {
+                    let budget = budgets.get(tier(name)).copied().unwrap_or(0);
+                    let pct = (budget > 0).then(|| (window.tokens.total() * 100 / budget).min(100));
+                    if readiness == Readiness::Ready {
+                        match pct {
+                            Some(pct) => reasons.push(format!("{}% of window used", pct)),
+                            None => reasons.push("window usage unknown".to_string()),
+                        }
+                        reasons.push(format!("window resets {}", clock_time(window.end)));
+                    }
+                    pct
+                }
+            };
+
+            if let TokenStatus::Warning { hours } = status {
+                reasons.push(format!("token expires in {}h", hours));
+            }
+
+            Suggestion {
+                name: name.clone(),
+                readiness,
+                used_pct,
+                expiring: matches!(status, TokenStatus::Warning { .. }),
+                last_used_at: info.last_used_at,
+                limited_until: cooldown.map(|remaining| now + remaining),
+                reasons,
+            }
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| a.rank_key().cmp(&b.rank_key()).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(suggestions)
+}
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use crate::audit::AuditEvent;
+use crate::metadata::{normalize_tag, validate_tag, AccountsMetadata};
+use crate::table::Table;
+use crate::ui;
+use crate::error::SwitchError;
+
+/// Add tags to an account
+pub fn tag_add(name: &str, tags: &[String]) -> Result<()> {
+    let tags = parse_tags(tags)?;
+    let mut meta = AccountsMetadata::load()?;
+
+    let name = meta.resolve_account(name)?;
+    let name = name.as_str();
+    let Some(info) = meta.accounts.get_mut(name) else {
+        return Err(SwitchError::AccountNotFound(name.to_string()).into());
+    };
+    info.tags.extend(tags.iter().cloned());
+    meta.save()?;
+    AuditEvent::new("tag-add", Some(name))
+        .detail("tags", tags.join(","))
+        .record()?;
+
+    println!();
+    ui::success(format!("Tagged '{}' with {}", name.cyan(), tags.join(", ")));
+    println!();
+
+    Ok(())
+}
+
+/// Remove tags from an account
+pub fn tag_remove(name: &str, tags: &[String]) -> Result<()> {
+    let tags = parse_tags(tags)?;
+    let mut meta = AccountsMetadata::load()?;
+
+    let name = meta.resolve_account(name)?;
+    let name = name.as_str();
+    let Some(info) = meta.accounts.get_mut(name) else {
+        return Err(SwitchError::AccountNotFound(name.to_string()).into());
+    };
+    for tag in &tags {
+        info.tags.remove(tag);
+    }
+    meta.save()?;
+    AuditEvent::new("tag-remove", Some(name))
+        .detail("tags", tags.join(","))
+        .record()?;
+
+    println!();
+    ui::success(format!("Removed {} from '{}'", tags.join(", "), name.cyan()));
+    println!();
+
+    Ok(())
+}
+
+/// List every tag with the accounts carrying it
+pub fn tag_list() -> Result<()> {
+    let meta = AccountsMetadata::load()?;
+
+    let mut groups: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (name, info) in &meta.accounts {
+        for tag in &info.tags {
+            groups.entry(tag.as_str()).or_default().push(name.as_str());
+        }
+    }
+
+    println!();
+    if groups.is_empty() {
+        ui::hint("No tags yet. Use 'claude-switch tag add <name> <tag>' to create one.");
+        println!();
+        return Ok(());
+    }
+
+    let mut table = Table::new(["TAG", "ACCOUNTS"]);
+    for (tag, mut names) in groups {
+        names.sort();
+        table.add_row(vec![tag.cyan().to_string(), names.join(", ")]);
+    }
+
+    ui::table("Tags", &table);
+    println!();
+
+    Ok(())
+}
+
+fn parse_tags(tags: &[String]) -> Result<Vec<String>> {
+    tags.iter()
+        .map(|t| {
+            let tag = normalize_tag(t);
+            validate_tag(&tag)?;
+            Ok(tag)
+        })
+        .collect()
+}
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use crate::metadata::AccountsMetadata;
+use crate::table::Table;
+use crate::timefmt::{self, clock_time, local_date, local_time};
+use crate::ui;
+use crate::usage::{self, TokenCounts};
+
+/// How `usage` groups its totals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum UsageGrouping {
+    /// One row per account
+    Account,
+    /// Per account and local calendar day
+    Day,
+    /// Per account and 5-hour usage window
+    Window,
+    /// Per account and model
+    Model,
+}
+
+/// One row of the usage report
+#[derive(Debug, Clone, Serialize)]
+struct UsageRow {
+    account: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<String>,
+    messages: u64,
+    tokens: TokenCounts,
+    total: u64,
+}
+
+/// Report token usage per account from local transcripts
+pub fn usage(
+    by: UsageGrouping,
+    since: Option<&str>,
+    account: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let since = since.map(timefmt::parse_since).transpose()?;
+    let account = match account {
+        Some(input) => {
+            let meta = AccountsMetadata::load()?;
+            // Accounts removed since still have usage under their old name
+            Some(meta.resolve_account(input).unwrap_or_else(|_| input.to_string()))
+        }
+        None => None,
+    };
+
+    let mut records = usage::load_records(since)?;
+    if let Some(account) = &account {
+        records.retain(|r| &r.account == account);
+    }
+
+    let rows = match by {
+        UsageGrouping::Window => usage::windows(&records)
+            .into_iter()
+            .map(|w| UsageRow {
+                account: w.account,
+                group: Some(format!(
+                    "{} {} {}",
+                    local_time(w.start),
+                    ui::glyphs().range,
+                    clock_time(w.end)
+                )),
+                messages: w.messages,
+                total: w.tokens.total(),
+                tokens: w.tokens,
+            })
+            .collect(),
+        _ => {
+            let mut groups: BTreeMap<(String, Option<String>), (u64, TokenCounts)> = BTreeMap::new();
+            for record in &records {
+                let group = match by {
+                    UsageGrouping::Day => Some(local_date(record.at)),
+                    UsageGrouping::Model => Some(record.model.clone()),
+                    _ => None,
+                };
+                let entry = groups.entry((record.account.clone(), group)).or_default();
+                entry.0 += 1;
+                entry.1 += record.tokens;
+            }
+            groups
+                .into_iter()
+                .map(|((account, group), (messages, tokens))| UsageRow {
+                    account,
+                    group,
+                    messages,
+                    total: tokens.total(),
+                    tokens,
+                })
+                .collect::<Vec<_>>()
+        }
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    println!();
+    if rows.is_empty() {
+        ui::hint("No token usage found in Claude Code transcripts for this period.");
+        println!();
+        return Ok(());
+    }
+
+    let group_header = match by {
+        UsageGrouping::Account => None,
+        UsageGrouping::Day => Some("DAY"),
+        UsageGrouping::Window => Some("WINDOW"),
+        UsageGrouping::Model => Some("MODEL"),
+    };
+    let mut headers = vec!["ACCOUNT"];
+    headers.extend(group_header);
+    headers.extend(["MSGS", "INPUT", "OUTPUT", "CACHE WRITE", "CACHE READ", "TOTAL"]);
+
+    let mut table = Table::new(headers);
+    for row in &rows {
+        let mut cells = vec![row.account.cyan().to_string()];
+        cells.extend(row.group.clone());
+        cells.extend([
+            format_count(row.messages),
+            format_count(row.tokens.input),
+            format_count(row.tokens.output),
+            format_count(row.tokens.cache_creation),
+            format_count(row.tokens.cache_read),
+            format_count(row.total).bold().to_string(),
+        ]);
+        table.add_row(cells);
+    }
+
+    ui::table("Token Usage", &table);
+    println!();
+    if let Some(since) = since {
+        ui::hint(format!("  Since {}", local_time(since)));
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Format a count with thousands separators, e.g. `1,234,567`
+fn format_count(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
@@ -1,24 +1,54 @@
 use anyhow::Result;
 use colored::Colorize;
+use crate::audit::AuditEvent;
+use crate::config::Config;
 use crate::credentials::{Credentials, TokenStatus};
-use crate::metadata::AccountsMetadata;
+use crate::history::{self, Trigger};
+use crate::hooks::{self, HookEvent};
+use crate::metadata::{normalize_tag, AccountsMetadata, Borrow};
 use crate::paths;
+use crate::processes::{self, ClaudeProcess};
+use crate::select::best_account;
+use crate::timefmt::{self, clock_time, countdown, local_time};
+use crate::ui;
 use crate::error::SwitchError;
 
+/// What to do with Claude Code sessions still running on the old account
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RestartMode {
+    /// List them and remind the user to restart
+    #[default]
+    Warn,
+    /// Terminate them gracefully
+    Restart,
+    /// Terminate them, then resume with `claude --continue`
+    Relaunch,
+}
+
 /// Switch to a different account
-pub fn use_account(name: &str) -> Result<()> {
+pub fn use_account(name: &str, restart: RestartMode) -> Result<()> {
+    switch(name, Trigger::Manual, restart)
+}
+
+/// Switch to a different account, recording what caused the switch
+pub fn switch_account(name: &str, trigger: Trigger) -> Result<()> {
+    switch(name, trigger, RestartMode::Warn)
+}
+
+fn switch(name: &str, trigger: Trigger, restart: RestartMode) -> Result<()> {
+    let config = Config::load()?;
+
     // Load metadata
     let mut meta = AccountsMetadata::load()?;
 
-    // Check if account exists
-    if !meta.account_exists(name) {
-        return Err(SwitchError::AccountNotFound(name.to_string()).into());
-    }
+    // Resolve the account name (exact, case-insensitive or unique prefix)
+    let name = meta.resolve_account(name)?;
+    let name = name.as_str();
 
     // Check if already using this account
     if meta.current_account.as_deref() == Some(name) {
         println!();
-        println!("{} Already using account '{}'", "ℹ".blue(), name.cyan());
+        ui::info(format!("Already using account '{}'", name.cyan()));
         println!();
         return Ok(());
     }
@@ -31,18 +61,37 @@ pub fn use_account(name: &str) -> Result<()> {
     let status = TokenStatus::from_expires_at(target_creds.expires_at());
     if let TokenStatus::Expired = status {
         println!();
-        println!(
-            "{} Token for '{}' is expired. You may need to re-login after switching.",
-            "⚠".yellow(),
+        ui::warning(format!(
+            "Token for '{}' is expired. You may need to re-login after switching.",
             name
-        );
+        ));
         println!();
     }
 
+    // Hooks may veto the switch before anything changes
+    let hook_vars = [
+        ("FROM", meta.current_account.clone().unwrap_or_default()),
+        ("TO", name.to_string()),
+        ("ACCOUNT", name.to_string()),
+        ("TRIGGER", trigger.label().to_string()),
+    ];
+    hooks::run(&config.hooks, HookEvent::PreUse, &hook_vars)?;
+
+    // Sessions keep the credentials they started with
+    let running = processes::find_running()?;
+    if !running.is_empty() && restart == RestartMode::Warn {
+        println!();
+        ui::warning(format!(
+            "{} Claude Code session(s) running on the current account:",
+            running.len()
+        ));
+        print_processes(&running);
+    }
+
     // Backup current credentials
     let current_creds = Credentials::load_active()?;
     let backup_path = paths::backup_path()?;
-    current_creds.save_to(&backup_path)?;
+    current_creds.save_to_store(&backup_path)?;
 
     // Update backup timestamp in metadata
     meta.last_backup_at = Some(chrono::Utc::now().timestamp_millis());
@@ -55,18 +104,234 @@ pub fn use_account(name: &str) -> Result<()> {
     if let Some(info) = meta.accounts.get_mut(name) {
         info.last_used_at = now;
     }
+    meta.previous_account = meta.current_account.take();
     meta.current_account = Some(name.to_string());
+    if trigger != Trigger::Pin {
+        // Choosing an account by other means ends any borrow
+        meta.borrow = None;
+    }
     meta.save()?;
+    history::record_switch(meta.previous_account.as_deref(), name, trigger, &config.history)?;
 
+    let mut event = AuditEvent::new("use", Some(name))
+        .detail("trigger", trigger.label())
+        .fingerprint(name, &target_creds);
+    if let Some(previous) = meta.previous_account.as_deref() {
+        event = event.detail("from", previous).fingerprint(previous, &current_creds);
+    }
+    event.record()?;
+
+    hooks::run(&config.hooks, HookEvent::PostUse, &hook_vars)?;
+
+    println!();
+    ui::success("Backed up current credentials");
+    ui::success(format!("Switched to '{}'", name.cyan()));
     println!();
-    println!("{} Backed up current credentials", "✓".green());
-    println!("{} Switched to '{}'", "✓".green(), name.cyan());
+
+    if restart == RestartMode::Warn || running.is_empty() {
+        println!(
+            "{}",
+            format!("{}  Restart Claude Code to apply changes", ui::glyphs().warn)
+                .yellow()
+                .bold()
+        );
+        if !running.is_empty() {
+            ui::hint("   Pass --restart to 'use' to have running sessions restarted for you");
+        }
+        println!();
+        return Ok(());
+    }
+
+    restart_sessions(&running, restart == RestartMode::Relaunch)
+}
+
+/// Print running sessions with their PIDs and working directories
+fn print_processes(processes: &[ClaudeProcess]) {
+    for process in processes {
+        let cwd = process
+            .cwd
+            .as_ref()
+            .map_or_else(|| "unknown directory".to_string(), |p| p.display().to_string());
+        println!("    PID {:<8} {}", process.pid, cwd.dimmed());
+    }
+}
+
+/// Terminate running sessions and optionally resume them
+fn restart_sessions(running: &[ClaudeProcess], relaunch: bool) -> Result<()> {
+    let survivors = processes::terminate(running);
+    let stopped: Vec<&ClaudeProcess> = running
+        .iter()
+        .filter(|p| !survivors.iter().any(|s| s.pid == p.pid))
+        .collect();
+
+    if !stopped.is_empty() {
+        ui::success(format!("Stopped {} Claude Code session(s)", stopped.len()));
+    }
+    if !survivors.is_empty() {
+        ui::warning("These sessions did not exit and still use the old account:");
+        print_processes(&survivors);
+    }
+    println!();
+
+    if !relaunch || stopped.is_empty() {
+        return Ok(());
+    }
+
+    // Only one session can take over this terminal: prefer the one started
+    // here, and print how to resume the others
+    let here = std::env::current_dir().ok();
+    let foreground = stopped
+        .iter()
+        .position(|p| p.cwd.is_some() && p.cwd == here)
+        .unwrap_or(0);
+
+    for (i, process) in stopped.iter().enumerate() {
+        if i != foreground {
+            if let Some(cwd) = &process.cwd {
+                ui::hint(format!("  Resume in another terminal: cd {} && claude --continue", cwd.display()));
+            }
+        }
+    }
+
+    let process = stopped[foreground];
+    ui::info(format!(
+        "Resuming session in {}",
+        process.cwd.as_ref().map_or_else(|| ".".to_string(), |p| p.display().to_string())
+    ));
     println!();
-    println!(
-        "{}",
-        "⚠  Restart Claude Code to apply changes".yellow().bold()
-    );
+
+    let mut cmd = process.relaunch_command();
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // exec only returns on failure
+        let err = cmd.exec();
+        Err(anyhow::Error::new(err).context("Could not relaunch Claude Code"))
+    }
+    #[cfg(not(unix))]
+    {
+        cmd.status()?;
+        Ok(())
+    }
+}
+
+/// Switch to an account for a limited time, then back to the one active
+/// now
+pub fn borrow_account(name: &str, duration: &str, restart: RestartMode) -> Result<()> {
+    let duration = timefmt::parse_duration(duration)?;
+    let mut meta = AccountsMetadata::load()?;
+    let name = meta.resolve_account(name)?;
+
+    // Extending or chaining borrows keeps the original account to return to
+    let previous = meta.borrow.clone();
+    let return_to = match &previous {
+        Some(borrow) => borrow.return_to.clone(),
+        None if meta.current_account.as_deref() == Some(name.as_str()) => {
+            return Err(SwitchError::AlreadyActive(name).into());
+        }
+        None => meta.current_account.clone(),
+    };
+
+    // Borrowing the account a borrow would return to just ends the borrow.
+    // Clear it here: `switch` stops early if the account is already in use.
+    if return_to.as_deref() == Some(name.as_str()) {
+        meta.borrow = None;
+        meta.save()?;
+        println!();
+        ui::info(format!("Back on '{}', borrow ended", name.cyan()));
+        return switch(&name, Trigger::Manual, restart);
+    }
+
+    // Record the borrow first: with --restart --relaunch the switch ends
+    // by handing this process over to Claude Code
+    let until = chrono::Utc::now().timestamp_millis() + duration;
+    meta.borrow = Some(Borrow {
+        return_to: return_to.clone(),
+        until,
+    });
+    meta.save()?;
+
+    let mut event = AuditEvent::new("borrow", Some(&name)).detail("until", local_time(until));
+    if let Some(return_to) = &return_to {
+        event = event.detail("returnTo", return_to.as_str());
+    }
+    event.record()?;
+
     println!();
+    match return_to {
+        Some(return_to) => ui::info(format!(
+            "Borrowing '{}' until {} (in {}), then switching back to '{}'",
+            name.cyan(),
+            clock_time(until),
+            countdown(duration),
+            return_to.cyan()
+        )),
+        None => ui::warning("No account was active before, so there is nothing to switch back to"),
+    }
+
+    if let Err(e) = switch(&name, Trigger::Pin, restart) {
+        let mut meta = AccountsMetadata::load()?;
+        meta.borrow = previous;
+        meta.save()?;
+        return Err(e);
+    }
 
     Ok(())
 }
+
+/// Switch back if a borrow from `use --for` has run out. Returns the
+/// account switched back to, if any.
+pub fn revert_expired_borrow() -> Result<Option<String>> {
+    let mut meta = AccountsMetadata::load()?;
+    let Some(borrow) = meta.borrow.clone() else {
+        return Ok(None);
+    };
+    if borrow.remaining().is_some() {
+        return Ok(None);
+    }
+
+    let borrowed = meta.current_account.clone().unwrap_or_default();
+    if borrow.return_to.as_deref() == Some(borrowed.as_str()) {
+        // Already back on the account, e.g. after a manual switch that
+        // didn't go through `use`
+        meta.borrow = None;
+        meta.save()?;
+        return Ok(None);
+    }
+
+    match borrow.return_to {
+        Some(return_to) if paths::account_credentials_path(&return_to)?.exists() => {
+            println!();
+            ui::info(format!(
+                "Borrow of '{}' ended at {}, switching back to '{}'",
+                borrowed.cyan(),
+                clock_time(borrow.until),
+                return_to.cyan()
+            ));
+            switch_account(&return_to, Trigger::Auto)?;
+            Ok(Some(return_to))
+        }
+        _ => {
+            meta.borrow = None;
+            meta.save()?;
+            Ok(None)
+        }
+    }
+}
+
+/// Switch to the best healthy account carrying a tag
+pub fn use_tag(tag: &str, restart: RestartMode) -> Result<()> {
+    let tag = normalize_tag(tag);
+    let meta = AccountsMetadata::load()?;
+
+    let candidates = meta.accounts_with_tag(&tag);
+    if candidates.is_empty() {
+        return Err(SwitchError::NoAccountsWithTag(tag).into());
+    }
+
+    let name = best_account(&meta, &candidates)
+        .ok_or_else(|| SwitchError::NoHealthyAccount(tag.clone()))?
+        .to_string();
+
+    use_account(&name, restart)
+}
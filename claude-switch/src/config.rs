@@ -0,0 +1,121 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::error::{Result, SwitchError};
+use crate::hooks::HooksConfig;
+use crate::paths;
+use crate::rules::Rule;
+use crate::timefmt;
+use crate::ui::{ColorChoice, GlyphSet};
+
+/// User configuration (`~/.claude/accounts/config.json`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Colour output; the `--color` flag takes precedence
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<ColorChoice>,
+
+    /// Glyph set for status marks and rules
+    pub glyphs: GlyphSet,
+
+    /// How `next` / `prev` pick accounts
+    pub rotation: RotationConfig,
+
+    /// Retention limits for the switch history
+    pub history: HistoryConfig,
+
+    /// Commands run around account changes
+    pub hooks: HooksConfig,
+
+    /// Settings for the `run` supervisor
+    pub run: RunConfig,
+
+    /// Time-based switching rules for `auto` and the daemon
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<Rule>,
+}
+
+/// How `next` chooses the account to rotate to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RotationStrategy {
+    /// Least recently used account first
+    #[default]
+    Lru,
+    /// Fixed order (`rotation.order`, then alphabetical)
+    Order,
+    /// Highest `rotation.priority` first, least recently used on ties
+    Priority,
+}
+
+/// Rotation settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RotationConfig {
+    pub strategy: RotationStrategy,
+
+    /// Account names in rotation order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<String>,
+
+    /// Per-account priority; higher is picked first (default 0)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub priority: BTreeMap<String, i32>,
+}
+
+/// `run` supervisor settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RunConfig {
+    /// Most account rotations in one `run` before giving up
+    pub max_rotations: u32,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self { max_rotations: 3 }
+    }
+}
+
+/// Switch history retention
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HistoryConfig {
+    /// Keep at most this many records
+    pub max_entries: usize,
+
+    /// Drop records older than this duration (e.g. `90d`); unset keeps
+    /// everything within `maxEntries`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<String>,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 10_000,
+            max_age: Some("365d".to_string()),
+        }
+    }
+}
+
+impl HistoryConfig {
+    /// `max_age` in milliseconds
+    pub fn max_age_ms(&self) -> Result<Option<i64>> {
+        self.max_age.as_deref().map(timefmt::parse_duration).transpose()
+    }
+}
+
+impl Config {
+    /// Load the config file, or return defaults if it doesn't exist
+    pub fn load() -> Result<Self> {
+        let path = paths::config_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&contents)
+            .map_err(|e| SwitchError::InvalidConfig(path.display().to_string(), e.to_string()))
+    }
+}
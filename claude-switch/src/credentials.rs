@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 use crate::error::{Result, SwitchError};
 use crate::paths;
+use crate::store;
+use crate::ui;
 
 /// OAuth credentials for Claude AI
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,16 +43,32 @@ impl Credentials {
         Self::load_from(&path)
     }
 
-    /// Read credentials from a specific path
+    /// Read credentials from a specific path, decrypting them if they are
+    /// stored encrypted (which may ask for the passphrase)
     pub fn load_from(path: &Path) -> Result<Self> {
         if !path.exists() {
             return Err(SwitchError::NoCredentials);
         }
         let contents = std::fs::read_to_string(path)?;
+        if store::is_envelope(&contents) {
+            let plaintext = store::decrypt(&store::key()?, &contents, path)?;
+            return Ok(serde_json::from_slice(&plaintext)?);
+        }
         let creds: Credentials = serde_json::from_str(&contents)?;
         Ok(creds)
     }
 
+    /// Read credentials without ever prompting: encrypted files are only
+    /// read when the store key is already available
+    pub fn peek_from(path: &Path) -> Option<Self> {
+        let contents = std::fs::read_to_string(path).ok()?;
+        if store::is_envelope(&contents) {
+            let plaintext = store::decrypt(&store::cached_key()?, &contents, path).ok()?;
+            return serde_json::from_slice(&plaintext).ok();
+        }
+        serde_json::from_str(&contents).ok()
+    }
+
     /// Save credentials to a specific path with secure permissions
     pub fn save_to(&self, path: &Path) -> Result<()> {
         let contents = serde_json::to_string_pretty(self)?;
@@ -65,6 +83,15 @@ impl Credentials {
         Ok(())
     }
 
+    /// Save credentials into the account store, encrypted if the store is
+    pub fn save_to_store(&self, path: &Path) -> Result<()> {
+        if !store::is_encrypted()? {
+            return self.save_to(path);
+        }
+        let plaintext = zeroize::Zeroizing::new(serde_json::to_vec_pretty(self)?);
+        store::write_private(path, &store::encrypt(&store::key()?, &plaintext)?)
+    }
+
     /// Save as the active credentials
     pub fn save_active(&self) -> Result<()> {
         let path = paths::credentials_path()?;
@@ -80,6 +107,20 @@ impl Credentials {
     pub fn expires_at(&self) -> i64 {
         self.claude_ai_oauth.expires_at
     }
+
+    /// Check whether two credential sets hold the same login
+    pub fn same_login(&self, other: &Credentials) -> bool {
+        self.claude_ai_oauth.refresh_token == other.claude_ai_oauth.refresh_token
+            || self.claude_ai_oauth.access_token == other.claude_ai_oauth.access_token
+    }
+}
+
+/// Short, non-reversible fingerprint of a token for display and logs
+pub fn token_fingerprint(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest(token.as_bytes());
+    let hex: String = digest.iter().take(6).map(|b| format!("{:02x}", b)).collect();
+    format!("sha256:{}", hex)
 }
 
 /// Token status based on expiration
@@ -105,13 +146,30 @@ impl TokenStatus {
         }
     }
 
+    /// Short lowercase label, as used by filters and templates
+    pub fn label(&self) -> &'static str {
+        match self {
+            TokenStatus::Valid { .. } => "valid",
+            TokenStatus::Warning { .. } => "warning",
+            TokenStatus::Expired => "expired",
+        }
+    }
+
     /// Get a colored display string
     pub fn display(&self) -> String {
-        use colored::Colorize;
         match self {
-            TokenStatus::Valid { days } => format!("{} Valid ({} days)", "✓".green(), days),
-            TokenStatus::Warning { hours } => format!("{} {} hours", "⚠".yellow(), hours),
-            TokenStatus::Expired => format!("{} Expired", "✗".red()),
+            TokenStatus::Valid { days } => format!("{} Valid ({} days)", ui::ok_mark(), days),
+            TokenStatus::Warning { hours } => format!("{} {} hours", ui::warn_mark(), hours),
+            TokenStatus::Expired => format!("{} Expired", ui::error_mark()),
+        }
+    }
+
+    /// Plain (uncoloured) glyph for this status
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            TokenStatus::Valid { .. } => ui::glyphs().ok,
+            TokenStatus::Warning { .. } => ui::glyphs().warn,
+            TokenStatus::Expired => ui::glyphs().error,
         }
     }
 }
@@ -8,18 +8,120 @@ pub enum SwitchError {
     #[error("Account '{0}' not found")]
     AccountNotFound(String),
 
+    #[error("Account '{0}' not found. Did you mean: {1}?")]
+    AccountNotFoundSuggest(String, String),
+
+    #[error("Account name '{0}' is ambiguous. Matches: {1}")]
+    AmbiguousAccount(String, String),
+
     #[error("Account '{0}' already exists. Use --force to overwrite.")]
     AccountExists(String),
 
+    #[error("No active account. Specify an account name.")]
+    NoActiveAccount,
+
     #[error("Cannot remove active account '{0}'. Switch to another account first.")]
     CannotRemoveActive(String),
 
     #[error("Invalid account name '{0}'. Use only letters, numbers, hyphens, and underscores (2-30 chars).")]
     InvalidAccountName(String),
 
+    #[error("'{0}' is already an alias for '{1}'")]
+    AliasExists(String, String),
+
+    #[error("Alias '{0}' not found")]
+    AliasNotFound(String),
+
+    #[error("Alias '{0}' would shadow an account with the same name")]
+    AliasIsAccountName(String),
+
+    #[error("Invalid tag '{0}'. Use only letters, numbers, hyphens, and underscores (1-30 chars).")]
+    InvalidTag(String),
+
+    #[error("No accounts tagged '{0}'")]
+    NoAccountsWithTag(String),
+
+    #[error("No healthy account tagged '{0}'. All tokens in that group are expired.")]
+    NoHealthyAccount(String),
+
+    #[error("No other available account to rotate to. All others are expired, rate-limited or missing credentials.")]
+    NoRotationCandidate,
+
+    #[error("No account is available right now. All are expired, rate-limited or missing credentials.")]
+    NoAvailableAccount,
+
+    #[error("No command given. Use e.g. 'claude-switch run -- claude'.")]
+    NoCommand,
+
+    #[error("No previous account to go back to")]
+    NoPreviousAccount,
+
+    #[error("Account metadata has version {0}, which is newer than this claude-switch supports. Please upgrade.")]
+    MetadataTooNew(u32),
+
     #[error("No accounts saved yet. Use 'claude-switch add <name>' to save your first account.")]
     NoAccountsSaved,
 
+    #[error("Claude Code already has a status line ('{0}'). Use --force to replace it.")]
+    StatusLineConfigured(String),
+
+    #[error("Settings file '{0}' is not a JSON object")]
+    InvalidSettings(String),
+
+    #[error("Invalid filter '{0}'. Use key=value or key!=value with key one of: name, type, status, tier, tag.")]
+    InvalidFilter(String),
+
+    #[error("Editor '{0}' exited with an error; note left unchanged")]
+    EditorFailed(String),
+
+    #[error("Invalid config file '{0}': {1}")]
+    InvalidConfig(String, String),
+
+    #[error("Invalid duration '{0}'. Use e.g. 5h, 90m, 1h30m or 2d.")]
+    InvalidDuration(String),
+
+    #[error("Invalid time '{0}'. Use HH:MM, 'YYYY-MM-DD HH:MM' or RFC 3339.")]
+    InvalidTime(String),
+
+    #[error("'{0}' is in the past")]
+    TimeInPast(String),
+
+    #[error("'{0}' is already active; there is nothing to borrow")]
+    AlreadyActive(String),
+
+    #[error("pre-use hook '{0}' {1}; switch aborted")]
+    HookAborted(String, String),
+
+    #[error("Invalid rule '{0}': {1}")]
+    InvalidRule(String, String),
+
+    #[error("Audit log verification failed: {0}")]
+    AuditLogTampered(String),
+
+    #[error("Wrong passphrase for the encrypted account store")]
+    WrongPassphrase,
+
+    #[error("The account store is encrypted. Run this in a terminal to enter the passphrase, run 'claude-switch unlock' with an agent, or set CLAUDE_SWITCH_PASSPHRASE.")]
+    StoreLocked,
+
+    #[error("Could not decrypt '{0}'. The file may be corrupt.")]
+    DecryptFailed(String),
+
+    #[error("The account store is not encrypted")]
+    StoreNotEncrypted,
+
+    #[error("Passphrases do not match")]
+    PassphraseMismatch,
+
+    #[error("The passphrase must not be empty")]
+    EmptyPassphrase,
+
+    #[error("No unlock agent running. Start one with: eval \"$(claude-switch agent)\"")]
+    AgentNotRunning,
+
+    #[error("Unlock agent: {0}")]
+    AgentError(String),
+
     #[error("Home directory not found")]
     NoHomeDir,
 
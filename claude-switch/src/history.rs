@@ -0,0 +1,351 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use crate::audit;
+use crate::config::HistoryConfig;
+use crate::error::Result;
+use crate::paths;
+use crate::store;
+
+/// What caused a switch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Trigger {
+    /// `use` or `suggest --use`
+    #[default]
+    Manual,
+    /// Picked automatically, e.g. by a rule or the supervisor
+    Auto,
+    /// Temporary switch that reverts on its own
+    Pin,
+    /// `next` / `prev`
+    Rotation,
+}
+
+impl Trigger {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Trigger::Manual => "manual",
+            Trigger::Auto => "auto",
+            Trigger::Pin => "pin",
+            Trigger::Rotation => "rotation",
+        }
+    }
+}
+
+/// One account switch, as stored in the append-only history file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwitchRecord {
+    /// When the switch happened (ms)
+    pub at: i64,
+
+    /// Previously active account, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+
+    /// Newly active account
+    pub to: String,
+
+    /// What caused the switch (records from older versions are manual)
+    #[serde(default)]
+    pub trigger: Trigger,
+
+    /// Machine the switch happened on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+}
+
+/// Append a switch to the history file, then apply the retention limits
+pub fn record_switch(from: Option<&str>, to: &str, trigger: Trigger, retention: &HistoryConfig) -> Result<()> {
+    let record = SwitchRecord {
+        at: chrono::Utc::now().timestamp_millis(),
+        from: from.map(String::from),
+        to: to.to_string(),
+        trigger,
+        host: hostname::get().ok().map(|h| h.to_string_lossy().into_owned()),
+    };
+
+    paths::ensure_accounts_dir()?;
+    append(&paths::history_path()?, &record, retention)
+}
+
+fn append(path: &Path, record: &SwitchRecord, retention: &HistoryConfig) -> Result<()> {
+    let _lock = lock(path)?;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    restrict_permissions(path)?;
+
+    prune(path, retention, record.at)
+}
+
+/// Drop records beyond the configured count or older than the configured
+/// age. The file is only rewritten when something is dropped. Callers
+/// hold the lock.
+fn prune(path: &Path, retention: &HistoryConfig, now: i64) -> Result<()> {
+    let mut records = load_from(path)?;
+    let before = records.len();
+
+    if let Some(max_age) = retention.max_age_ms()? {
+        let cutoff = now - max_age;
+        records.retain(|r| r.at >= cutoff);
+    }
+    if records.len() > retention.max_entries {
+        records.drain(..records.len() - retention.max_entries);
+    }
+
+    if records.len() != before {
+        save(path, &records)?;
+    }
+    Ok(())
+}
+
+/// Point every record mentioning `old` at `new`, so history survives a
+/// `rename`
+pub fn rename_account(old: &str, new: &str) -> Result<()> {
+    rename_in(&paths::history_path()?, old, new)
+}
+
+fn rename_in(path: &Path, old: &str, new: &str) -> Result<()> {
+    let _lock = lock(path)?;
+    let mut records = load_from(path)?;
+    let mut changed = false;
+
+    for record in &mut records {
+        if record.from.as_deref() == Some(old) {
+            record.from = Some(new.to_string());
+            changed = true;
+        }
+        if record.to == old {
+            record.to = new.to_string();
+            changed = true;
+        }
+    }
+
+    if changed {
+        save(path, &records)?;
+    }
+    Ok(())
+}
+
+/// Replace the history file with `records`, via a temporary file so a
+/// crash can't leave it half-written. Callers hold the lock.
+fn save(path: &Path, records: &[SwitchRecord]) -> Result<()> {
+    let mut contents = String::new();
+    for record in records {
+        contents.push_str(&serde_json::to_string(record)?);
+        contents.push('\n');
+    }
+    store::write_private(path, &contents)
+}
+
+/// Lock held while the history is appended to or rewritten. A sidecar
+/// file is locked, since rewriting replaces the history file itself.
+fn lock(path: &Path) -> Result<std::fs::File> {
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(path.with_extension("jsonl.lock"))?;
+    audit::lock_exclusive(&file)?;
+    Ok(file)
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// Load every switch record, oldest first, skipping unreadable lines
+pub fn load() -> Result<Vec<SwitchRecord>> {
+    load_from(&paths::history_path()?)
+}
+
+fn load_from(path: &Path) -> Result<Vec<SwitchRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file = std::fs::File::open(path)?;
+    let mut records: Vec<SwitchRecord> = BufReader::new(file)
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect();
+    records.sort_by_key(|r| r.at);
+    Ok(records)
+}
+
+/// Work out which account was active at `at` (ms).
+///
+/// Uses the last switch at or before that time. Before the first recorded
+/// switch, the account switched away from is assumed; with no history at
+/// all, the currently active account is.
+pub fn account_at<'a>(
+    records: &'a [SwitchRecord],
+    at: i64,
+    current: Option<&'a str>,
+) -> Option<&'a str> {
+    match records.iter().rev().find(|r| r.at <= at) {
+        Some(record) => Some(record.to.as_str()),
+        None => match records.first() {
+            Some(first) => first.from.as_deref(),
+            None => current,
+        },
+    }
+}
+
+/// A stretch of time during which one account was active
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub account: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Turn switch records into sessions: each switch starts a session on the
+/// new account that lasts until the next switch, or until `now` for the
+/// last one. Time before the first recorded switch is not counted.
+pub fn sessions(records: &[SwitchRecord], now: i64) -> Vec<Session> {
+    records
+        .iter()
+        .enumerate()
+        .map(|(i, record)| Session {
+            account: record.to.clone(),
+            start: record.at,
+            end: records.get(i + 1).map_or(now, |next| next.at).max(record.at),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 60 * 60 * 1000;
+    const DAY: i64 = 24 * HOUR;
+
+    fn record(at: i64, from: Option<&str>, to: &str) -> SwitchRecord {
+        SwitchRecord {
+            at,
+            from: from.map(String::from),
+            to: to.to_string(),
+            trigger: Trigger::Manual,
+            host: None,
+        }
+    }
+
+    fn history() -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        (dir, path)
+    }
+
+    fn keep(max_entries: usize, max_age: Option<&str>) -> HistoryConfig {
+        HistoryConfig {
+            max_entries,
+            max_age: max_age.map(String::from),
+        }
+    }
+
+    #[test]
+    fn account_at_uses_last_switch_at_or_before() {
+        let records = [record(100, Some("a"), "b"), record(200, Some("b"), "c")];
+        assert_eq!(account_at(&records, 150, Some("c")), Some("b"));
+        assert_eq!(account_at(&records, 200, Some("c")), Some("c"));
+        assert_eq!(account_at(&records, 199, Some("c")), Some("b"));
+        assert_eq!(account_at(&records, 500, Some("x")), Some("c"));
+    }
+
+    #[test]
+    fn account_at_before_first_switch_and_without_history() {
+        let records = [record(100, Some("a"), "b")];
+        assert_eq!(account_at(&records, 99, Some("b")), Some("a"));
+        assert_eq!(account_at(&[record(100, None, "b")], 99, Some("b")), None);
+        assert_eq!(account_at(&[], 99, Some("current")), Some("current"));
+    }
+
+    #[test]
+    fn sessions_run_until_the_next_switch() {
+        let records = [record(100, None, "a"), record(250, Some("a"), "b")];
+        let found = sessions(&records, 400);
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].account.as_str(), found[0].start, found[0].end), ("a", 100, 250));
+        assert_eq!((found[1].account.as_str(), found[1].start, found[1].end), ("b", 250, 400));
+
+        // A clock that went backwards never gives a negative session
+        let late = sessions(&[record(500, None, "a")], 400);
+        assert_eq!(late[0].end, 500);
+    }
+
+    #[test]
+    fn prune_keeps_newest_entries() {
+        let (_dir, path) = history();
+        let now = 10 * DAY;
+        let records: Vec<_> = (0..5).map(|i| record(now - i * HOUR, None, &format!("a{}", i))).collect();
+        save(&path, &records).unwrap();
+
+        prune(&path, &keep(3, None), now).unwrap();
+        let kept: Vec<_> = load_from(&path).unwrap().into_iter().map(|r| r.to).collect();
+        assert_eq!(kept, ["a2", "a1", "a0"]);
+    }
+
+    #[test]
+    fn prune_drops_old_entries() {
+        let (_dir, path) = history();
+        let now = 100 * DAY;
+        save(
+            &path,
+            &[
+                record(now - 31 * DAY, None, "old"),
+                record(now - 30 * DAY, None, "edge"),
+                record(now - DAY, None, "new"),
+            ],
+        )
+        .unwrap();
+
+        prune(&path, &keep(100, Some("30d")), now).unwrap();
+        let kept: Vec<_> = load_from(&path).unwrap().into_iter().map(|r| r.to).collect();
+        assert_eq!(kept, ["edge", "new"]);
+    }
+
+    #[test]
+    fn rename_rewrites_from_and_to() {
+        let (_dir, path) = history();
+        save(&path, &[record(100, None, "old"), record(200, Some("old"), "other")]).unwrap();
+
+        rename_in(&path, "old", "new").unwrap();
+        let records = load_from(&path).unwrap();
+        assert_eq!(records[0].to, "new");
+        assert_eq!(records[1].from.as_deref(), Some("new"));
+        assert_eq!(records[1].to, "other");
+    }
+
+    #[test]
+    fn concurrent_appends_are_kept() {
+        let (_dir, path) = history();
+        std::thread::scope(|scope| {
+            for t in 0..4 {
+                let path = &path;
+                scope.spawn(move || {
+                    for i in 0..10 {
+                        let at = chrono::Utc::now().timestamp_millis();
+                        append(path, &record(at, None, &format!("t{}-{}", t, i)), &keep(1000, None)).unwrap();
+                    }
+                });
+            }
+        });
+        assert_eq!(load_from(&path).unwrap().len(), 40);
+    }
+}
@@ -0,0 +1,263 @@
+//! User hooks run around account changes.
+//!
+//! Hooks come from two places, run in this order: shell commands listed
+//! under `hooks` in the config file, then executables in
+//! `~/.claude/accounts/hooks/` named after the event (`post-use`) or
+//! placed in a directory of that name plus `.d` (`post-use.d/*`).
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use crate::error::{Result, SwitchError};
+use crate::paths;
+use crate::timefmt;
+use crate::ui;
+
+/// How long a hook may run when `hooks.timeout` isn't set
+const DEFAULT_TIMEOUT: &str = "30s";
+
+/// Points at which hooks run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    /// Before a switch; a failing hook aborts it
+    PreUse,
+    PostUse,
+    PostAdd,
+    PostRemove,
+    /// A saved token will expire within a day (checked by the daemon)
+    OnExpiryWarning,
+}
+
+impl HookEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookEvent::PreUse => "pre-use",
+            HookEvent::PostUse => "post-use",
+            HookEvent::PostAdd => "post-add",
+            HookEvent::PostRemove => "post-remove",
+            HookEvent::OnExpiryWarning => "on-expiry-warning",
+        }
+    }
+}
+
+/// Hook commands and limits from the config file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// Longest a single hook may run, e.g. `10s` (default 30s)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+
+    #[serde(rename = "pre-use", skip_serializing_if = "Vec::is_empty")]
+    pub pre_use: Vec<String>,
+
+    #[serde(rename = "post-use", skip_serializing_if = "Vec::is_empty")]
+    pub post_use: Vec<String>,
+
+    #[serde(rename = "post-add", skip_serializing_if = "Vec::is_empty")]
+    pub post_add: Vec<String>,
+
+    #[serde(rename = "post-remove", skip_serializing_if = "Vec::is_empty")]
+    pub post_remove: Vec<String>,
+
+    #[serde(rename = "on-expiry-warning", skip_serializing_if = "Vec::is_empty")]
+    pub on_expiry_warning: Vec<String>,
+}
+
+impl HooksConfig {
+    fn commands(&self, event: HookEvent) -> &[String] {
+        match event {
+            HookEvent::PreUse => &self.pre_use,
+            HookEvent::PostUse => &self.post_use,
+            HookEvent::PostAdd => &self.post_add,
+            HookEvent::PostRemove => &self.post_remove,
+            HookEvent::OnExpiryWarning => &self.on_expiry_warning,
+        }
+    }
+}
+
+/// A single hook to run
+enum Hook {
+    Shell(String),
+    Executable(PathBuf),
+}
+
+impl Hook {
+    fn label(&self) -> String {
+        match self {
+            Hook::Shell(command) => command.clone(),
+            Hook::Executable(path) => path.display().to_string(),
+        }
+    }
+
+    fn command(&self) -> Command {
+        match self {
+            Hook::Shell(command) => {
+                let mut cmd = Command::new("sh");
+                cmd.arg("-c").arg(command);
+                cmd
+            }
+            Hook::Executable(path) => Command::new(path),
+        }
+    }
+}
+
+/// Run every hook for `event` with `vars` (names without the
+/// `CLAUDE_SWITCH_` prefix) in the environment.
+///
+/// A failing or timed-out `pre-use` hook stops the remaining hooks and
+/// returns an error so the switch is aborted. Failures of other hooks are
+/// reported as warnings, since the change has already happened.
+pub fn run(config: &HooksConfig, event: HookEvent, vars: &[(&str, String)]) -> Result<()> {
+    let timeout = timefmt::parse_duration(config.timeout.as_deref().unwrap_or(DEFAULT_TIMEOUT))?;
+    let timeout = Duration::from_millis(timeout as u64);
+
+    for hook in discover(config, event)? {
+        let mut cmd = hook.command();
+        cmd.stdin(Stdio::null())
+            .env("CLAUDE_SWITCH_EVENT", event.name());
+        for (name, value) in vars {
+            cmd.env(format!("CLAUDE_SWITCH_{}", name), value);
+        }
+
+        if let Err(reason) = run_with_timeout(cmd, timeout) {
+            if event == HookEvent::PreUse {
+                return Err(SwitchError::HookAborted(hook.label(), reason));
+            }
+            ui::warning(format!("{} hook '{}' {}", event.name(), hook.label(), reason));
+        }
+    }
+
+    Ok(())
+}
+
+/// Config commands first, then `hooks/<event>`, then `hooks/<event>.d/*`
+/// in name order
+fn discover(config: &HooksConfig, event: HookEvent) -> Result<Vec<Hook>> {
+    let mut hooks: Vec<Hook> = config
+        .commands(event)
+        .iter()
+        .map(|c| Hook::Shell(c.clone()))
+        .collect();
+
+    let dir = paths::hooks_dir()?;
+    let single = dir.join(event.name());
+    if single.is_file() {
+        hooks.push(Hook::Executable(single));
+    }
+
+    let multi = dir.join(format!("{}.d", event.name()));
+    if multi.is_dir() {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&multi)?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.is_file())
+            .collect();
+        files.sort();
+        hooks.extend(files.into_iter().map(Hook::Executable));
+    }
+
+    hooks.retain(|hook| match hook {
+        Hook::Executable(path) if !is_executable(path) => {
+            ui::warning(format!("Skipping hook '{}': not executable", path.display()));
+            false
+        }
+        _ => true,
+    });
+
+    Ok(hooks)
+}
+
+/// Run a hook to completion, killing it and everything it started once
+/// `timeout` passes. Returns a short reason on failure.
+fn run_with_timeout(mut cmd: Command, timeout: Duration) -> std::result::Result<(), String> {
+    // Its own process group, so a timeout can reach the hook's children too
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    let mut child = cmd.spawn().map_err(|e| format!("could not start: {}", e))?;
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => {
+                return Err(match status.code() {
+                    Some(code) => format!("exited with status {}", code),
+                    None => "was killed by a signal".to_string(),
+                })
+            }
+            Ok(None) if Instant::now() >= deadline => {
+                kill_group(&mut child);
+                let _ = child.wait();
+                return Err(format!("timed out after {}", format_timeout(timeout)));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(25)),
+            Err(e) => return Err(format!("could not be waited on: {}", e)),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn kill_group(child: &mut std::process::Child) {
+    // SAFETY: killpg has no memory-safety preconditions; the group id is
+    // the hook's pid because it was spawned with `process_group(0)`
+    unsafe {
+        libc::killpg(child.id() as libc::pid_t, libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_group(child: &mut std::process::Child) {
+    let _ = child.kill();
+}
+
+/// `30s`, or milliseconds when the timeout isn't whole seconds
+fn format_timeout(timeout: Duration) -> String {
+    if timeout.subsec_millis() == 0 && timeout.as_secs() > 0 {
+        format!("{}s", timeout.as_secs())
+    } else {
+        format!("{}ms", timeout.as_millis())
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &std::path::Path) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_timeouts() {
+        assert_eq!(format_timeout(Duration::from_secs(30)), "30s");
+        assert_eq!(format_timeout(Duration::from_millis(250)), "250ms");
+        assert_eq!(format_timeout(Duration::from_millis(1500)), "1500ms");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn timeout_kills_the_whole_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("survived");
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(format!("(sleep 1; touch '{}') & sleep 5", marker.display()));
+
+        let reason = run_with_timeout(cmd, Duration::from_millis(200)).unwrap_err();
+        assert_eq!(reason, "timed out after 200ms");
+
+        std::thread::sleep(Duration::from_millis(1500));
+        assert!(!marker.exists(), "a process started by the hook outlived the timeout");
+    }
+}
@@ -1,5 +1,19 @@
+pub mod agent;
+pub mod audit;
 pub mod commands;
+pub mod config;
 pub mod credentials;
 pub mod error;
+pub mod history;
+pub mod hooks;
 pub mod metadata;
 pub mod paths;
+pub mod processes;
+pub mod rules;
+pub mod select;
+pub mod store;
+pub mod table;
+pub mod timefmt;
+pub mod transcripts;
+pub mod ui;
+pub mod usage;
@@ -1,11 +1,32 @@
 use clap::{Parser, Subcommand};
 use colored::Colorize;
+use std::path::PathBuf;
+use commands::list::{Column, ListOptions, SortKey};
+use commands::stats::StatsPeriod;
+use commands::usage::UsageGrouping;
+use commands::use_account::RestartMode;
+use config::{Config, RotationStrategy};
+use ui::{ColorChoice, GlyphSet};
 
+mod agent;
+mod audit;
 mod commands;
+mod config;
 mod credentials;
 mod error;
+mod history;
+mod hooks;
 mod metadata;
 mod paths;
+mod processes;
+mod rules;
+mod select;
+mod store;
+mod table;
+mod timefmt;
+mod transcripts;
+mod ui;
+mod usage;
 
 #[derive(Parser)]
 #[command(name = "claude-switch")]
@@ -13,6 +34,10 @@ mod paths;
 #[command(version)]
 #[command(about = "Switch between Claude Pro accounts - zero token usage", long_about = None)]
 struct Cli {
+    /// When to use colours (default: auto, or `color` in config.json)
+    #[arg(long, global = true, value_enum)]
+    color: Option<ColorChoice>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -30,12 +55,155 @@ enum Commands {
     },
 
     /// List all saved accounts with status
-    List,
+    List {
+        /// Sort order
+        #[arg(short, long, value_enum, default_value_t = SortKey::Name)]
+        sort: SortKey,
+
+        /// Reverse the sort order
+        #[arg(short, long)]
+        reverse: bool,
+
+        /// Only show matching accounts, e.g. status=expired, type=max, tag=client
+        #[arg(short, long)]
+        filter: Vec<String>,
+
+        /// Only show accounts with this tag (shorthand for --filter tag=<tag>)
+        #[arg(short, long)]
+        tag: Option<String>,
+
+        /// Columns to show, comma separated
+        #[arg(short, long, value_enum, value_delimiter = ',')]
+        columns: Vec<Column>,
+
+        /// Show absolute local times next to relative ones
+        #[arg(short, long)]
+        long: bool,
+    },
 
     /// Switch to a different account (requires restart)
     Use {
         /// Account name to switch to
+        #[arg(required_unless_present = "tag")]
+        name: Option<String>,
+
+        /// Switch to the best healthy account with this tag instead
+        #[arg(short, long, conflicts_with = "name")]
+        tag: Option<String>,
+
+        /// Only for this long (e.g. 90m), then switch back automatically
+        #[arg(long = "for", value_name = "DURATION", conflicts_with = "tag")]
+        duration: Option<String>,
+
+        /// Gracefully stop running Claude Code sessions after switching
+        #[arg(long)]
+        restart: bool,
+
+        /// With --restart, resume the stopped session with --continue
+        #[arg(long, requires = "restart")]
+        relaunch: bool,
+    },
+
+    /// Mark an account as rate-limited so rotation skips it
+    Limited {
+        /// Account name
         name: String,
+
+        /// Limited until this time (HH:MM, 'YYYY-MM-DD HH:MM' or RFC 3339)
+        #[arg(long, conflicts_with_all = ["duration", "clear"])]
+        until: Option<String>,
+
+        /// Limited for this long from now, e.g. 5h or 90m (default: 5h)
+        #[arg(long = "for", value_name = "DURATION", conflicts_with = "clear")]
+        duration: Option<String>,
+
+        /// Clear the rate limit
+        #[arg(long)]
+        clear: bool,
+    },
+
+    /// Record cooldowns from usage-limit messages in Claude Code transcripts
+    ScanLimits {
+        /// Re-read every transcript from the start
+        #[arg(long)]
+        rescan: bool,
+    },
+
+    /// Show recorded account switches
+    History {
+        /// Only switches to or from this account
+        #[arg(short, long)]
+        account: Option<String>,
+
+        /// Only switches since a duration ago (7d) or a date (YYYY-MM-DD)
+        #[arg(short, long)]
+        since: Option<String>,
+
+        /// Show at most this many of the most recent switches (0 for all)
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Active time per account, from the switch history
+    Stats {
+        /// Only count time since a duration ago (30d) or a date (YYYY-MM-DD)
+        #[arg(short, long)]
+        since: Option<String>,
+
+        /// Break active time down by day or week
+        #[arg(short, long, value_enum, default_value_t = StatsPeriod::Week)]
+        by: StatsPeriod,
+
+        /// Print JSON instead of tables
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Rank accounts by how much headroom they likely have right now
+    Suggest {
+        /// Switch to the top pick
+        #[arg(long = "use")]
+        switch: bool,
+    },
+
+    /// Token usage per account from local Claude Code transcripts
+    Usage {
+        /// Group totals by account, day, 5-hour window or model
+        #[arg(short, long, value_enum, default_value_t = UsageGrouping::Account)]
+        by: UsageGrouping,
+
+        /// Only count usage since a duration ago (7d) or a date (YYYY-MM-DD)
+        #[arg(short, long)]
+        since: Option<String>,
+
+        /// Only show one account
+        #[arg(short, long)]
+        account: Option<String>,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Run periodic maintenance (limit scanning) in the foreground
+    Daemon {
+        /// Time between runs, e.g. 60s or 5m
+        #[arg(short, long, default_value = "60s")]
+        interval: String,
+    },
+
+    /// Rotate to the next available account
+    Next {
+        /// Override the configured rotation strategy
+        #[arg(short, long, value_enum)]
+        strategy: Option<RotationStrategy>,
+    },
+
+    /// Go back to the previous account
+    Prev {
+        /// Override the configured rotation strategy
+        #[arg(short, long, value_enum)]
+        strategy: Option<RotationStrategy>,
     },
 
     /// Remove a saved account
@@ -55,24 +223,411 @@ enum Commands {
 
     /// Show current account info
     Current,
+
+    /// Add, remove or list account aliases
+    Alias {
+        #[command(subcommand)]
+        action: AliasAction,
+    },
+
+    /// Run Claude Code, rotating accounts and resuming when it hits a usage limit
+    Run {
+        /// Most rotations before giving up (default: run.maxRotations, 3)
+        #[arg(long)]
+        max_rotations: Option<u32>,
+
+        /// Command to run, e.g. `-- claude --model opus`
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+
+    /// Switch to the account the time-based rules select right now
+    Auto,
+
+    /// List or test the time-based switching rules
+    Rules {
+        #[command(subcommand)]
+        action: RulesAction,
+    },
+
+    /// Inspect or verify the audit log of store changes
+    Audit {
+        #[command(subcommand)]
+        action: AuditAction,
+    },
+
+    /// Encrypt saved credentials with a passphrase
+    Encrypt,
+
+    /// Start an agent that holds the store key, like ssh-agent
+    /// (use with `eval "$(claude-switch agent)"`)
+    Agent {
+        /// Forget the key after this long without use, e.g. 30m or 8h
+        #[arg(short, long, default_value = "1h")]
+        timeout: String,
+
+        /// Listen on this socket instead of one in $XDG_RUNTIME_DIR
+        #[arg(long)]
+        socket: Option<PathBuf>,
+
+        /// Stay in the foreground instead of detaching
+        #[arg(short, long)]
+        foreground: bool,
+    },
+
+    /// Give the running agent the store key
+    Unlock,
+
+    /// Make the running agent forget the store key
+    Lock,
+
+    /// Decrypt saved credentials and turn encryption off
+    Decrypt,
+
+    /// Add, remove or list account tags
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+
+    /// Show or set the note attached to an account
+    Note {
+        /// Account name
+        name: String,
+
+        /// New note text (omit to print the current note)
+        #[arg(trailing_var_arg = true)]
+        text: Vec<String>,
+
+        /// Edit the note in $VISUAL / $EDITOR
+        #[arg(short, long, conflicts_with_all = ["text", "clear"])]
+        edit: bool,
+
+        /// Remove the note
+        #[arg(long, conflicts_with = "text")]
+        clear: bool,
+    },
+
+    /// Show everything known about an account
+    Show {
+        /// Account name (default: the active account)
+        name: Option<String>,
+    },
+
+    /// Print a one-line account summary for prompts and status bars
+    Status {
+        /// Output template ({name}, {type}, {expires_in}, {status}, {status_icon})
+        #[arg(short, long, default_value = "{status_icon} {name}")]
+        format: String,
+
+        /// Cache the summary, keyed by credentials/metadata modification times
+        #[arg(long)]
+        cache: bool,
+    },
+
+    /// Status line segment for Claude Code (reads session JSON on stdin)
+    Statusline {
+        /// Output template (same placeholders as `status`)
+        #[arg(short, long, default_value = "{name} · {type} · {expires_in}")]
+        format: String,
+    },
+
+    /// Configure Claude Code to use `claude-switch statusline`
+    InstallStatusline {
+        /// Claude config dir to install into (default: ~/.claude)
+        #[arg(long)]
+        config_dir: Option<PathBuf>,
+
+        /// Replace an existing status line command
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AliasAction {
+    /// Add an alias for an account
+    Add {
+        /// New alias
+        alias: String,
+
+        /// Account the alias points to
+        name: String,
+    },
+
+    /// Remove an alias
+    Remove {
+        /// Alias to remove
+        alias: String,
+    },
+
+    /// List all aliases
+    List,
+}
+
+#[derive(Subcommand)]
+enum TagAction {
+    /// Add one or more tags to an account
+    Add {
+        /// Account name
+        name: String,
+
+        /// Tags to add
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+
+    /// Remove one or more tags from an account
+    Remove {
+        /// Account name
+        name: String,
+
+        /// Tags to remove
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+
+    /// List all tags and their accounts
+    List,
+}
+
+#[derive(Subcommand)]
+enum RulesAction {
+    /// List the configured rules
+    List,
+
+    /// Show which rule applies at a time and the account it selects
+    Test {
+        /// Time to evaluate (HH:MM, 'YYYY-MM-DD HH:MM' or RFC 3339; default: now)
+        #[arg(long)]
+        at: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuditAction {
+    /// Show the most recent audit entries
+    List {
+        /// Show at most this many entries (0 for all)
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Check the hash chain for edits and truncation
+    Verify,
 }
 
 fn main() {
     let cli = Cli::parse();
 
+    // The prompt and status line run on every redraw: skip the colour setup
+    // and only read the config for glyphs, so a broken config.json can't
+    // break them
+    match &cli.command {
+        Commands::Status { format, cache } => {
+            init_glyphs_for(format);
+            match commands::status(format, *cache) {
+                Ok(code) => exit(code),
+                Err(e) => exit_with_error(e),
+            }
+        }
+        Commands::Statusline { format } => {
+            init_glyphs_for(format);
+            if let Err(e) = commands::statusline(format) {
+                exit_with_error(e);
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            ui::init(cli.color.unwrap_or_default(), GlyphSet::default());
+            exit_with_error(e.into());
+        }
+    };
+    ui::init(
+        cli.color.or(config.color).unwrap_or_default(),
+        config.glyphs,
+    );
+
+    // Finish an expired `use --for` before changing the store. Read-only
+    // commands leave it alone (switching back may need the passphrase),
+    // and the daemon checks on its own.
+    if changes_store(&cli.command) {
+        if let Err(e) = commands::revert_expired_borrow() {
+            ui::warning(format!("Could not switch back after borrowing: {}", e));
+        }
+    }
+
     let result = match cli.command {
         Commands::Add { name, force } => commands::add(&name, force),
-        Commands::List => commands::list(),
-        Commands::Use { name } => commands::use_account(&name),
+        Commands::List {
+            sort,
+            reverse,
+            mut filter,
+            tag,
+            columns,
+            long,
+        } => {
+            filter.extend(tag.map(|t| format!("tag={}", t)));
+            commands::list(&ListOptions {
+                sort,
+                reverse,
+                filters: filter,
+                columns,
+                long,
+            })
+        }
+        Commands::Use {
+            name,
+            tag,
+            duration,
+            restart,
+            relaunch,
+        } => {
+            let restart = match (restart, relaunch) {
+                (_, true) => RestartMode::Relaunch,
+                (true, false) => RestartMode::Restart,
+                (false, false) => RestartMode::Warn,
+            };
+            match (name, tag, duration) {
+                (_, Some(tag), _) => commands::use_tag(&tag, restart),
+                (Some(name), None, Some(duration)) => {
+                    commands::borrow_account(&name, &duration, restart)
+                }
+                (Some(name), None, None) => commands::use_account(&name, restart),
+                (None, None, _) => unreachable!("clap requires a name or --tag"),
+            }
+        }
+        Commands::Limited {
+            name,
+            until,
+            duration,
+            clear,
+        } => commands::limited(&name, until.as_deref(), duration.as_deref(), clear),
+        Commands::ScanLimits { rescan } => commands::scan_limits(rescan),
+        Commands::History {
+            account,
+            since,
+            limit,
+        } => commands::history(account.as_deref(), since.as_deref(), limit),
+        Commands::Stats { since, by, json } => commands::stats(since.as_deref(), by, json),
+        Commands::Suggest { switch } => commands::suggest(switch),
+        Commands::Usage {
+            by,
+            since,
+            account,
+            json,
+        } => commands::usage(by, since.as_deref(), account.as_deref(), json),
+        Commands::Daemon { interval } => commands::daemon(&interval),
+        Commands::Next { strategy } => commands::next(strategy),
+        Commands::Prev { strategy } => commands::prev(strategy),
         Commands::Remove { name } => commands::remove(&name),
         Commands::Rename { old, new } => commands::rename(&old, &new),
         Commands::Current => commands::current(),
+        Commands::Alias { action } => match action {
+            AliasAction::Add { alias, name } => commands::alias_add(&alias, &name),
+            AliasAction::Remove { alias } => commands::alias_remove(&alias),
+            AliasAction::List => commands::alias_list(),
+        },
+        Commands::Run {
+            max_rotations,
+            command,
+        } => match commands::run(&command, max_rotations) {
+            Ok(code) => exit(code),
+            Err(e) => Err(e),
+        },
+        Commands::Auto => commands::auto(),
+        Commands::Rules { action } => match action {
+            RulesAction::List => commands::rules_list(),
+            RulesAction::Test { at } => commands::rules_test(at.as_deref()),
+        },
+        Commands::Audit { action } => match action {
+            AuditAction::List { limit } => commands::audit_list(limit),
+            AuditAction::Verify => commands::audit_verify(),
+        },
+        Commands::Encrypt => commands::encrypt(),
+        Commands::Agent {
+            timeout,
+            socket,
+            foreground,
+        } => commands::agent(&timeout, socket, foreground),
+        Commands::Unlock => commands::unlock(),
+        Commands::Lock => commands::lock(),
+        Commands::Decrypt => commands::decrypt(),
+        Commands::Tag { action } => match action {
+            TagAction::Add { name, tags } => commands::tag_add(&name, &tags),
+            TagAction::Remove { name, tags } => commands::tag_remove(&name, &tags),
+            TagAction::List => commands::tag_list(),
+        },
+        Commands::Note {
+            name,
+            text,
+            edit,
+            clear,
+        } => {
+            let text = (!text.is_empty()).then(|| text.join(" "));
+            commands::note(&name, text.as_deref(), edit, clear)
+        }
+        Commands::Show { name } => commands::show(name.as_deref()),
+        Commands::Status { .. } | Commands::Statusline { .. } => unreachable!("handled above"),
+        Commands::InstallStatusline { config_dir, force } => {
+            commands::install_statusline(config_dir, force)
+        }
     };
 
     if let Err(e) = result {
-        eprintln!();
-        eprintln!("{} {}", "Error:".red().bold(), e);
-        eprintln!();
-        std::process::exit(1);
+        exit_with_error(e);
     }
+    store::forget_key();
+}
+
+/// Whether a command modifies accounts, metadata or the active credentials
+fn changes_store(command: &Commands) -> bool {
+    match command {
+        Commands::Add { .. }
+        | Commands::Use { .. }
+        | Commands::Limited { .. }
+        | Commands::ScanLimits { .. }
+        | Commands::Next { .. }
+        | Commands::Prev { .. }
+        | Commands::Remove { .. }
+        | Commands::Rename { .. }
+        | Commands::Run { .. }
+        | Commands::Auto
+        | Commands::Encrypt
+        | Commands::Decrypt => true,
+        Commands::Suggest { switch } => *switch,
+        Commands::Alias { action } => !matches!(action, AliasAction::List),
+        Commands::Tag { action } => !matches!(action, TagAction::List),
+        Commands::Note {
+            text, edit, clear, ..
+        } => !text.is_empty() || *edit || *clear,
+        _ => false,
+    }
+}
+
+/// Honour the `glyphs` setting in prompt formats that show the status icon.
+/// The config is only read when needed, and a broken one is ignored.
+fn init_glyphs_for(format: &str) {
+    if format.contains("{status_icon}") {
+        let glyphs = Config::load().map(|c| c.glyphs).unwrap_or_default();
+        ui::init(ColorChoice::Never, glyphs);
+    }
+}
+
+fn exit_with_error(e: anyhow::Error) -> ! {
+    eprintln!();
+    eprintln!("{} {}", "Error:".red().bold(), e);
+    eprintln!();
+    exit(1);
+}
+
+/// Exit without unwinding, wiping the cached store key first
+fn exit(code: i32) -> ! {
+    store::forget_key();
+    std::process::exit(code);
 }
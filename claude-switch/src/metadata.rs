@@ -1,10 +1,10 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use crate::error::{Result, SwitchError};
 use crate::paths;
 
 /// Information about a single saved account
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AccountInfo {
     #[serde(rename = "addedAt")]
     pub added_at: i64,
@@ -20,8 +20,42 @@ pub struct AccountInfo {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
+
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<String>,
+
+    /// Rate-limited until this time (ms); cleared once it passes
+    #[serde(rename = "limitedUntil", default, skip_serializing_if = "Option::is_none")]
+    pub limited_until: Option<i64>,
+}
+
+impl AccountInfo {
+    /// The note, if one is set and non-empty
+    pub fn note(&self) -> Option<&str> {
+        self.notes.as_deref().filter(|n| !n.trim().is_empty())
+    }
+
+    /// Remaining cooldown in milliseconds, if the account is rate-limited
+    pub fn cooldown_remaining(&self) -> Option<i64> {
+        let now = chrono::Utc::now().timestamp_millis();
+        self.limited_until
+            .map(|until| until - now)
+            .filter(|remaining| *remaining > 0)
+    }
+
+    /// Check if the account carries a tag
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
 }
 
+/// Current metadata format version
+///
+/// - 1: initial format (shared with the Claude Code plugin)
+/// - 2: adds per-account `tags`
+/// - 3: adds top-level `aliases`
+pub const METADATA_VERSION: u32 = 3;
+
 /// Metadata for all saved accounts
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountsMetadata {
@@ -30,19 +64,53 @@ pub struct AccountsMetadata {
     #[serde(rename = "currentAccount", skip_serializing_if = "Option::is_none")]
     pub current_account: Option<String>,
 
+    /// Account that was active before the current one, for `prev`
+    #[serde(rename = "previousAccount", default, skip_serializing_if = "Option::is_none")]
+    pub previous_account: Option<String>,
+
     #[serde(rename = "lastBackupAt", skip_serializing_if = "Option::is_none")]
     pub last_backup_at: Option<i64>,
 
     pub accounts: HashMap<String, AccountInfo>,
+
+    /// Alternative names, mapping alias to account name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+
+    /// Temporary switch from `use --for`, reverted once it runs out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub borrow: Option<Borrow>,
+}
+
+/// A time-boxed switch and where to go back to afterwards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Borrow {
+    /// Account to switch back to, if one was active before
+    #[serde(rename = "returnTo", default, skip_serializing_if = "Option::is_none")]
+    pub return_to: Option<String>,
+
+    /// When to switch back (ms)
+    pub until: i64,
+}
+
+impl Borrow {
+    /// Time left before switching back, if any
+    pub fn remaining(&self) -> Option<i64> {
+        let remaining = self.until - chrono::Utc::now().timestamp_millis();
+        (remaining > 0).then_some(remaining)
+    }
 }
 
 impl Default for AccountsMetadata {
     fn default() -> Self {
         Self {
-            version: 1,
+            version: METADATA_VERSION,
             current_account: None,
+            previous_account: None,
             last_backup_at: None,
             accounts: HashMap::new(),
+            aliases: BTreeMap::new(),
+            borrow: None,
         }
     }
 }
@@ -55,10 +123,53 @@ impl AccountsMetadata {
             return Ok(Self::default());
         }
         let contents = std::fs::read_to_string(&path)?;
-        let meta: AccountsMetadata = serde_json::from_str(&contents)?;
+        let mut meta: AccountsMetadata = serde_json::from_str(&contents)?;
+        meta.migrate()?;
+        meta.clear_expired_cooldowns();
         Ok(meta)
     }
 
+    /// Forget cooldowns whose time has passed (persisted on the next save)
+    fn clear_expired_cooldowns(&mut self) {
+        for info in self.accounts.values_mut() {
+            if info.cooldown_remaining().is_none() {
+                info.limited_until = None;
+            }
+        }
+    }
+
+    /// Upgrade metadata written by older versions to the current format
+    fn migrate(&mut self) -> Result<()> {
+        if self.version > METADATA_VERSION {
+            return Err(SwitchError::MetadataTooNew(self.version));
+        }
+
+        if self.version < 2 {
+            // The plugin writes empty notes; v2 treats absent and empty alike
+            for info in self.accounts.values_mut() {
+                if info.note().is_none() {
+                    info.notes = None;
+                }
+            }
+        }
+
+        for info in self.accounts.values_mut() {
+            info.tags = std::mem::take(&mut info.tags)
+                .into_iter()
+                .map(|t| normalize_tag(&t))
+                .filter(|t| !t.is_empty())
+                .collect();
+        }
+
+        // Drop aliases that point nowhere or shadow a real account name
+        let accounts = &self.accounts;
+        self.aliases
+            .retain(|alias, target| accounts.contains_key(target) && !accounts.contains_key(alias));
+
+        self.version = METADATA_VERSION;
+        Ok(())
+    }
+
     /// Save metadata to file
     pub fn save(&self) -> Result<()> {
         paths::ensure_accounts_dir()?;
@@ -81,7 +192,6 @@ impl AccountsMetadata {
     }
 
     /// Get account info
-    #[allow(dead_code)]
     pub fn get_account(&self, name: &str) -> Option<&AccountInfo> {
         self.accounts.get(name)
     }
@@ -91,8 +201,17 @@ impl AccountsMetadata {
         self.accounts.insert(name, info);
     }
 
-    /// Remove an account
+    /// Remove an account along with its aliases
     pub fn remove_account(&mut self, name: &str) -> Option<AccountInfo> {
+        self.aliases.retain(|_, target| target != name);
+        if self.previous_account.as_deref() == Some(name) {
+            self.previous_account = None;
+        }
+        if let Some(borrow) = &mut self.borrow {
+            if borrow.return_to.as_deref() == Some(name) {
+                borrow.return_to = None;
+            }
+        }
         self.accounts.remove(name)
     }
 
@@ -103,6 +222,19 @@ impl AccountsMetadata {
             if self.current_account.as_deref() == Some(old) {
                 self.current_account = Some(new.to_string());
             }
+            if self.previous_account.as_deref() == Some(old) {
+                self.previous_account = Some(new.to_string());
+            }
+            if let Some(borrow) = &mut self.borrow {
+                if borrow.return_to.as_deref() == Some(old) {
+                    borrow.return_to = Some(new.to_string());
+                }
+            }
+            for target in self.aliases.values_mut() {
+                if target == old {
+                    *target = new.to_string();
+                }
+            }
             Ok(())
         } else {
             Err(SwitchError::AccountNotFound(old.to_string()))
@@ -119,6 +251,152 @@ impl AccountsMetadata {
     pub fn has_accounts(&self) -> bool {
         !self.accounts.is_empty()
     }
+
+    /// Resolve user input to a saved account name.
+    ///
+    /// Accepts the exact name, an alias, a case-insensitive match, or a
+    /// unique (case-insensitive) prefix. Misses suggest the closest names.
+    pub fn resolve_account(&self, input: &str) -> Result<String> {
+        if self.accounts.contains_key(input) {
+            return Ok(input.to_string());
+        }
+
+        let lower = input.to_lowercase();
+        if let Some(target) = self.alias_target(&lower) {
+            return Ok(target.to_string());
+        }
+        let mut names: Vec<&str> = self.accounts.keys().map(|s| s.as_str()).collect();
+        names.sort();
+
+        let exact: Vec<&str> = names
+            .iter()
+            .copied()
+            .filter(|n| n.to_lowercase() == lower)
+            .collect();
+        if let [name] = exact.as_slice() {
+            return Ok(name.to_string());
+        }
+
+        let prefixed: Vec<&str> = names
+            .iter()
+            .copied()
+            .filter(|n| n.to_lowercase().starts_with(&lower))
+            .collect();
+        match prefixed.as_slice() {
+            [name] => return Ok(name.to_string()),
+            [] => {}
+            candidates => {
+                return Err(SwitchError::AmbiguousAccount(
+                    input.to_string(),
+                    candidates.join(", "),
+                ))
+            }
+        }
+
+        let suggestions = closest_names(&lower, &names);
+        if suggestions.is_empty() {
+            Err(SwitchError::AccountNotFound(input.to_string()))
+        } else {
+            Err(SwitchError::AccountNotFoundSuggest(
+                input.to_string(),
+                suggestions.join(", "),
+            ))
+        }
+    }
+
+    /// Account an alias points to (aliases are stored lowercase)
+    pub fn alias_target(&self, alias: &str) -> Option<&str> {
+        self.aliases.get(&alias.to_lowercase()).map(|s| s.as_str())
+    }
+
+    /// Aliases pointing at an account, sorted
+    pub fn aliases_for(&self, name: &str) -> Vec<&str> {
+        self.aliases
+            .iter()
+            .filter(|(_, target)| target.as_str() == name)
+            .map(|(alias, _)| alias.as_str())
+            .collect()
+    }
+
+    /// Add an alias for an existing account
+    pub fn add_alias(&mut self, alias: &str, name: &str) -> Result<()> {
+        validate_account_name(alias)?;
+        let alias = alias.to_lowercase();
+
+        if !self.accounts.contains_key(name) {
+            return Err(SwitchError::AccountNotFound(name.to_string()));
+        }
+        if self.accounts.keys().any(|n| n.to_lowercase() == alias) {
+            return Err(SwitchError::AliasIsAccountName(alias));
+        }
+        if let Some(target) = self.aliases.get(&alias).filter(|t| t.as_str() != name) {
+            return Err(SwitchError::AliasExists(alias, target.clone()));
+        }
+
+        self.aliases.insert(alias, name.to_string());
+        Ok(())
+    }
+
+    /// Remove an alias, returning the account it pointed to
+    pub fn remove_alias(&mut self, alias: &str) -> Result<String> {
+        self.aliases
+            .remove(&alias.to_lowercase())
+            .ok_or_else(|| SwitchError::AliasNotFound(alias.to_string()))
+    }
+
+    /// Ensure a new account name doesn't collide with an alias
+    pub fn check_not_alias(&self, name: &str) -> Result<()> {
+        match self.alias_target(name) {
+            Some(target) => Err(SwitchError::AliasExists(name.to_lowercase(), target.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Names of accounts carrying a tag, sorted
+    pub fn accounts_with_tag(&self, tag: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .accounts
+            .iter()
+            .filter(|(_, info)| info.has_tag(tag))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+/// Up to three names within a small edit distance of `input`, closest first
+fn closest_names<'a>(input: &str, names: &[&'a str]) -> Vec<&'a str> {
+    let max_distance = (input.chars().count() / 3).max(2);
+
+    let mut scored: Vec<(usize, &str)> = names
+        .iter()
+        .map(|name| (strsim::levenshtein(input, &name.to_lowercase()), *name))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    scored.sort();
+
+    scored.into_iter().take(3).map(|(_, name)| name).collect()
+}
+
+/// Canonical form of a tag (trimmed, lowercase)
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+/// Validate a (normalized) tag
+pub fn validate_tag(tag: &str) -> Result<()> {
+    let is_valid = !tag.is_empty()
+        && tag.len() <= 30
+        && tag
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(SwitchError::InvalidTag(tag.to_string()))
+    }
 }
 
 /// Validate account name
@@ -135,3 +413,75 @@ pub fn validate_account_name(name: &str) -> Result<()> {
         Err(SwitchError::InvalidAccountName(name.to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta_with(names: &[&str]) -> AccountsMetadata {
+        let mut meta = AccountsMetadata::default();
+        for name in names {
+            meta.accounts.insert(name.to_string(), AccountInfo::default());
+        }
+        meta
+    }
+
+    #[test]
+    fn exact_match_beats_prefix() {
+        let meta = meta_with(&["work", "work-2", "personal"]);
+        assert_eq!(meta.resolve_account("work").unwrap(), "work");
+        assert_eq!(meta.resolve_account("WORK").unwrap(), "work");
+        assert_eq!(meta.resolve_account("work-").unwrap(), "work-2");
+    }
+
+    #[test]
+    fn unique_prefix_resolves_case_insensitively() {
+        let meta = meta_with(&["work", "Personal"]);
+        assert_eq!(meta.resolve_account("per").unwrap(), "Personal");
+        assert_eq!(meta.resolve_account("W").unwrap(), "work");
+    }
+
+    #[test]
+    fn ambiguous_prefix_lists_candidates() {
+        let meta = meta_with(&["client-a", "client-b", "personal"]);
+        match meta.resolve_account("cli") {
+            Err(SwitchError::AmbiguousAccount(input, candidates)) => {
+                assert_eq!(input, "cli");
+                assert_eq!(candidates, "client-a, client-b");
+            }
+            other => panic!("expected an ambiguous match, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn aliases_resolve_before_prefixes() {
+        let mut meta = meta_with(&["work", "personal", "pair"]);
+        meta.add_alias("pa", "personal").unwrap();
+        assert_eq!(meta.resolve_account("PA").unwrap(), "personal");
+        assert!(matches!(
+            meta.add_alias("Work", "personal"),
+            Err(SwitchError::AliasIsAccountName(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_names_suggest_close_matches() {
+        let meta = meta_with(&["work", "personal"]);
+        assert!(matches!(
+            meta.resolve_account("persnal"),
+            Err(SwitchError::AccountNotFoundSuggest(_, s)) if s == "personal"
+        ));
+        assert!(matches!(
+            meta.resolve_account("zzzzzz"),
+            Err(SwitchError::AccountNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn account_names_cannot_leave_the_store() {
+        for name in ["../x", "a/b", ".credentials", "x", ""] {
+            assert!(validate_account_name(name).is_err(), "{:?}", name);
+        }
+        assert!(validate_account_name("client_a-2").is_ok());
+    }
+}
@@ -13,6 +13,16 @@ pub fn credentials_path() -> Result<PathBuf> {
     Ok(claude_dir()?.join(".credentials.json"))
 }
 
+/// Get the Claude Code settings file
+pub fn settings_path() -> Result<PathBuf> {
+    Ok(claude_dir()?.join("settings.json"))
+}
+
+/// Get the directory holding Claude Code session transcripts
+pub fn projects_dir() -> Result<PathBuf> {
+    Ok(claude_dir()?.join("projects"))
+}
+
 /// Get the accounts storage directory
 pub fn accounts_dir() -> Result<PathBuf> {
     Ok(claude_dir()?.join("accounts"))
@@ -33,6 +43,46 @@ pub fn backup_path() -> Result<PathBuf> {
     Ok(accounts_dir()?.join(".credentials.backup.json"))
 }
 
+/// Get the path to the claude-switch config file
+pub fn config_path() -> Result<PathBuf> {
+    Ok(accounts_dir()?.join("config.json"))
+}
+
+/// Get the path to the switch history log
+pub fn history_path() -> Result<PathBuf> {
+    Ok(accounts_dir()?.join("history.jsonl"))
+}
+
+/// Get the directory holding hook executables
+pub fn hooks_dir() -> Result<PathBuf> {
+    Ok(accounts_dir()?.join("hooks"))
+}
+
+/// Get the path to the hash-chained audit log
+pub fn audit_log_path() -> Result<PathBuf> {
+    Ok(accounts_dir()?.join("audit.log"))
+}
+
+/// Get the path to the audit log head record
+pub fn audit_head_path() -> Result<PathBuf> {
+    Ok(accounts_dir()?.join(".audit.head.json"))
+}
+
+/// Get the path to the encrypted store header
+pub fn store_header_path() -> Result<PathBuf> {
+    Ok(accounts_dir()?.join(".store.json"))
+}
+
+/// Get the path to the transcript scan offsets
+pub fn scan_state_path() -> Result<PathBuf> {
+    Ok(accounts_dir()?.join(".scan-limits.state.json"))
+}
+
+/// Get the path to the `status` command cache
+pub fn status_cache_path() -> Result<PathBuf> {
+    Ok(accounts_dir()?.join(".status.cache.json"))
+}
+
 /// Get the path to a specific account's credentials
 pub fn account_credentials_path(name: &str) -> Result<PathBuf> {
     Ok(credentials_dir()?.join(format!("{}.json", name)))
@@ -0,0 +1,185 @@
+//! Finding and restarting running Claude Code sessions.
+//!
+//! A session keeps the credentials it started with, so after a switch it
+//! has to be restarted to pick up the new account. Detection reads
+//! `/proc` and only works on Linux; elsewhere no sessions are reported.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+use crate::error::Result;
+use crate::paths;
+
+/// How long to wait for sessions to exit after SIGTERM
+const TERMINATE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A running Claude Code process
+#[derive(Debug, Clone)]
+pub struct ClaudeProcess {
+    pub pid: i32,
+    pub cwd: Option<PathBuf>,
+    pub args: Vec<String>,
+
+    /// Start time in clock ticks since boot, to tell the process apart
+    /// from a later one that reuses its pid
+    pub start_time: Option<u64>,
+}
+
+impl ClaudeProcess {
+    /// Command that resumes the most recent conversation in the same
+    /// working directory
+    pub fn relaunch_command(&self) -> Command {
+        // Reuse the original binary when it was started directly
+        let program = self
+            .args
+            .first()
+            .filter(|arg0| is_claude_binary(arg0))
+            .map_or("claude", |s| s.as_str());
+
+        let mut cmd = Command::new(program);
+        cmd.arg("--continue");
+        if let Some(cwd) = &self.cwd {
+            cmd.current_dir(cwd);
+        }
+        cmd
+    }
+}
+
+/// Claude Code processes of this user that use the same config directory
+/// as claude-switch
+#[cfg(target_os = "linux")]
+pub fn find_running() -> Result<Vec<ClaudeProcess>> {
+    let ours = canonical(&paths::claude_dir()?);
+    let me = std::process::id() as i32;
+
+    let mut found = Vec::new();
+    for entry in std::fs::read_dir("/proc")?.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse::<i32>().ok()) else {
+            continue;
+        };
+        if pid == me {
+            continue;
+        }
+
+        let dir = entry.path();
+        let Ok(cmdline) = std::fs::read(dir.join("cmdline")) else {
+            continue;
+        };
+        let args: Vec<String> = cmdline
+            .split(|b| *b == 0)
+            .filter(|a| !a.is_empty())
+            .map(|a| String::from_utf8_lossy(a).into_owned())
+            .collect();
+        if !is_claude_code(&args) {
+            continue;
+        }
+
+        // Other users' processes have an unreadable environment
+        let Some(config_dir) = config_dir_of(&dir) else {
+            continue;
+        };
+        if canonical(&config_dir) != ours {
+            continue;
+        }
+
+        found.push(ClaudeProcess {
+            pid,
+            cwd: std::fs::read_link(dir.join("cwd")).ok(),
+            args,
+            start_time: start_time(pid),
+        });
+    }
+
+    found.sort_by_key(|p| p.pid);
+    Ok(found)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn find_running() -> Result<Vec<ClaudeProcess>> {
+    Ok(Vec::new())
+}
+
+/// Ask each process to exit with SIGTERM and wait briefly. Returns the
+/// processes still running afterwards.
+///
+/// Processes that exited since they were found are skipped, so a new
+/// process that reused the pid is never signalled.
+#[cfg(unix)]
+pub fn terminate(processes: &[ClaudeProcess]) -> Vec<ClaudeProcess> {
+    for process in processes.iter().filter(|p| is_alive(p)) {
+        // SAFETY: kill has no memory-safety preconditions
+        unsafe {
+            libc::kill(process.pid, libc::SIGTERM);
+        }
+    }
+
+    let deadline = Instant::now() + TERMINATE_TIMEOUT;
+    loop {
+        let alive: Vec<ClaudeProcess> = processes.iter().filter(|p| is_alive(p)).cloned().collect();
+        if alive.is_empty() || Instant::now() >= deadline {
+            return alive;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+#[cfg(not(unix))]
+pub fn terminate(processes: &[ClaudeProcess]) -> Vec<ClaudeProcess> {
+    processes.to_vec()
+}
+
+/// Whether the process still runs, and is the same one that was found
+#[cfg(unix)]
+fn is_alive(process: &ClaudeProcess) -> bool {
+    // SAFETY: signal 0 only checks that the process exists
+    let exists = unsafe { libc::kill(process.pid, 0) == 0 };
+    exists && start_time(process.pid) == process.start_time
+}
+
+/// Field 22 of `/proc/<pid>/stat`. The command name before it can contain
+/// spaces and parentheses, so fields are counted from the last `)`.
+#[cfg(target_os = "linux")]
+fn start_time(pid: i32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(19)?.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn start_time(_pid: i32) -> Option<u64> {
+    None
+}
+
+/// Recognise `claude ...` and `node .../claude-code/cli.js ...`
+fn is_claude_code(args: &[String]) -> bool {
+    args.iter().take(2).any(|arg| {
+        is_claude_binary(arg) || arg.ends_with("claude-code/cli.js") || arg.ends_with("claude-code/cli.mjs")
+    })
+}
+
+fn is_claude_binary(arg: &str) -> bool {
+    Path::new(arg).file_name().is_some_and(|name| name == "claude")
+}
+
+/// Config directory a process uses: a non-empty `CLAUDE_CONFIG_DIR`,
+/// else `$HOME/.claude`, as Claude Code itself decides
+#[cfg(target_os = "linux")]
+fn config_dir_of(proc_dir: &Path) -> Option<PathBuf> {
+    let environ = std::fs::read(proc_dir.join("environ")).ok()?;
+    let mut home = None;
+    for var in environ.split(|b| *b == 0) {
+        let var = String::from_utf8_lossy(var);
+        if let Some(dir) = var.strip_prefix("CLAUDE_CONFIG_DIR=").filter(|d| !d.is_empty()) {
+            return Some(PathBuf::from(dir));
+        }
+        if let Some(dir) = var.strip_prefix("HOME=") {
+            home = Some(PathBuf::from(dir));
+        }
+    }
+    home.map(|h| h.join(".claude"))
+}
+
+#[cfg(target_os = "linux")]
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,303 @@
+//! Time-based switching rules from the config file.
+//!
+//! A rule names an account plus the days and time of day it applies to.
+//! When several rules match, the highest priority wins, then the one
+//! listed first.
+
+use chrono::{Datelike, TimeZone, Timelike, Weekday};
+use serde::{Deserialize, Serialize};
+use crate::error::{Result, SwitchError};
+use crate::metadata::AccountsMetadata;
+use crate::select;
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// One switching rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rule {
+    /// Optional label shown by `rules test`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Account (or alias) to use while the rule applies
+    pub account: String,
+
+    /// Days the rule applies on: `mon`..`sun`, ranges like `mon-fri`,
+    /// `weekdays` or `weekends`. Empty means every day.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<String>,
+
+    /// Start time (`HH:MM`, inclusive); defaults to midnight
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+
+    /// End time (`HH:MM`, exclusive); defaults to midnight. An end before
+    /// the start runs past midnight into the next day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+
+    /// IANA timezone for days and times, e.g. `Europe/Berlin`; defaults
+    /// to the local timezone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    /// Higher wins when several rules match
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl Rule {
+    /// Name for messages: the configured name or `#<n>` (1-based)
+    pub fn label(&self, index: usize) -> String {
+        self.name.clone().unwrap_or_else(|| format!("#{}", index + 1))
+    }
+
+    /// Human-readable summary of when the rule applies
+    pub fn describe(&self) -> String {
+        let days = if self.days.is_empty() {
+            "every day".to_string()
+        } else {
+            self.days.join(",")
+        };
+        let time = match (&self.from, &self.to) {
+            (None, None) => "all day".to_string(),
+            (from, to) => format!(
+                "{}-{}",
+                from.as_deref().unwrap_or("00:00"),
+                to.as_deref().unwrap_or("24:00")
+            ),
+        };
+        match &self.timezone {
+            Some(tz) => format!("{} {} ({})", days, time, tz),
+            None => format!("{} {}", days, time),
+        }
+    }
+
+    /// Check whether the rule applies at `at` (ms)
+    pub fn matches(&self, at: i64) -> Result<bool> {
+        let days = self.parse_days()?;
+        let from = self.parse_clock(self.from.as_deref(), 0)?;
+        let to = self.parse_clock(self.to.as_deref(), MINUTES_PER_DAY)?;
+        let (weekday, minute) = self.local_parts(at)?;
+        let on = |day: Weekday| days[day.num_days_from_monday() as usize];
+
+        Ok(if from < to {
+            on(weekday) && minute >= from && minute < to
+        } else if from == to {
+            on(weekday)
+        } else {
+            // Overnight: the part after midnight belongs to the day before
+            (on(weekday) && minute >= from) || (on(weekday.pred()) && minute < to)
+        })
+    }
+
+    fn invalid(&self, msg: String) -> SwitchError {
+        SwitchError::InvalidRule(self.name.clone().unwrap_or_else(|| self.account.clone()), msg)
+    }
+
+    /// Weekday and minute of the day at `at`, in the rule's timezone
+    fn local_parts(&self, at: i64) -> Result<(Weekday, u32)> {
+        let parts = |t: chrono::DateTime<chrono::FixedOffset>| (t.weekday(), t.hour() * 60 + t.minute());
+        match &self.timezone {
+            Some(name) => {
+                let tz: chrono_tz::Tz = name
+                    .parse()
+                    .map_err(|_| self.invalid(format!("unknown timezone '{}'", name)))?;
+                let t = tz.timestamp_millis_opt(at).single().ok_or_else(|| self.invalid("time out of range".into()))?;
+                Ok(parts(t.fixed_offset()))
+            }
+            None => {
+                let t = chrono::Local
+                    .timestamp_millis_opt(at)
+                    .single()
+                    .ok_or_else(|| self.invalid("time out of range".into()))?;
+                Ok(parts(t.fixed_offset()))
+            }
+        }
+    }
+
+    /// Minutes since midnight for `HH:MM` (`24:00` allowed as an end)
+    fn parse_clock(&self, value: Option<&str>, default: u32) -> Result<u32> {
+        let Some(value) = value else {
+            return Ok(default);
+        };
+        let invalid = || self.invalid(format!("invalid time '{}', use HH:MM", value));
+        let (h, m) = value.trim().split_once(':').ok_or_else(invalid)?;
+        let (h, m): (u32, u32) = (h.parse().map_err(|_| invalid())?, m.parse().map_err(|_| invalid())?);
+        if m >= 60 || h > 24 || (h == 24 && m > 0) {
+            return Err(invalid());
+        }
+        Ok(h * 60 + m)
+    }
+
+    /// Days the rule applies on, indexed from Monday
+    fn parse_days(&self) -> Result<[bool; 7]> {
+        if self.days.is_empty() {
+            return Ok([true; 7]);
+        }
+
+        let mut days = [false; 7];
+        for spec in &self.days {
+            let spec = spec.trim().to_lowercase();
+            match spec.as_str() {
+                "weekdays" => days[..5].fill(true),
+                "weekends" => days[5..].fill(true),
+                "daily" | "*" => days.fill(true),
+                _ => {
+                    let (start, end) = spec.split_once('-').unwrap_or((&spec, &spec));
+                    let start = self.parse_weekday(start)?;
+                    let end = self.parse_weekday(end)?;
+                    let mut day = start;
+                    loop {
+                        days[day.num_days_from_monday() as usize] = true;
+                        if day == end {
+                            break;
+                        }
+                        day = day.succ();
+                    }
+                }
+            }
+        }
+        Ok(days)
+    }
+
+    fn parse_weekday(&self, value: &str) -> Result<Weekday> {
+        value
+            .trim()
+            .parse::<Weekday>()
+            .map_err(|_| self.invalid(format!("unknown day '{}'", value)))
+    }
+}
+
+/// Rules that apply at `at`, with their config index, best first
+pub fn matching(rules: &[Rule], at: i64) -> Result<Vec<(usize, &Rule)>> {
+    let mut matched = Vec::new();
+    for (i, rule) in rules.iter().enumerate() {
+        if rule.matches(at)? {
+            matched.push((i, rule));
+        }
+    }
+    // Stable sort keeps config order among equal priorities
+    matched.sort_by_key(|(_, rule)| -rule.priority);
+    Ok(matched)
+}
+
+/// The account rules select at `at`: the best matching rule whose account
+/// exists and is available. Returns the rule index and account name.
+pub fn select(rules: &[Rule], meta: &AccountsMetadata, at: i64) -> Result<Option<(usize, String)>> {
+    for (i, rule) in matching(rules, at)? {
+        let Ok(account) = meta.resolve_account(&rule.account) else {
+            continue;
+        };
+        if select::is_available(meta, &account) {
+            return Ok(Some((i, account)));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Milliseconds for a UTC time in the week of Monday 2026-10-19
+    fn at(day: u32, hour: u32, minute: u32) -> i64 {
+        chrono::Utc
+            .with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn rule(account: &str, days: &[&str], from: Option<&str>, to: Option<&str>) -> Rule {
+        Rule {
+            name: None,
+            account: account.to_string(),
+            days: days.iter().map(|d| d.to_string()).collect(),
+            from: from.map(String::from),
+            to: to.map(String::from),
+            timezone: Some("UTC".to_string()),
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn weekday_range_with_exclusive_end() {
+        let work = rule("work", &["mon-fri"], Some("09:00"), Some("17:00"));
+        assert!(work.matches(at(19, 9, 0)).unwrap());
+        assert!(work.matches(at(23, 16, 59)).unwrap());
+        assert!(!work.matches(at(19, 17, 0)).unwrap());
+        assert!(!work.matches(at(19, 8, 59)).unwrap());
+        assert!(!work.matches(at(24, 12, 0)).unwrap());
+    }
+
+    #[test]
+    fn overnight_range_belongs_to_the_day_it_starts() {
+        let late = rule("late", &["fri"], Some("22:00"), Some("06:00"));
+        assert!(late.matches(at(23, 23, 0)).unwrap());
+        assert!(late.matches(at(24, 3, 0)).unwrap());
+        assert!(!late.matches(at(23, 3, 0)).unwrap());
+        assert!(!late.matches(at(24, 22, 30)).unwrap());
+    }
+
+    #[test]
+    fn day_ranges_wrap_around_the_week() {
+        let weekend = rule("home", &["sat-mon"], None, None);
+        assert!(weekend.matches(at(19, 12, 0)).unwrap());
+        assert!(weekend.matches(at(25, 12, 0)).unwrap());
+        assert!(!weekend.matches(at(20, 12, 0)).unwrap());
+    }
+
+    #[test]
+    fn missing_times_cover_the_whole_day() {
+        let all_day = rule("any", &["weekends"], None, Some("24:00"));
+        assert!(all_day.matches(at(24, 0, 0)).unwrap());
+        assert!(all_day.matches(at(25, 23, 59)).unwrap());
+        assert!(!all_day.matches(at(23, 23, 59)).unwrap());
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        let bad = [
+            rule("x", &[], Some("25:00"), None),
+            rule("x", &[], Some("09:60"), None),
+            rule("x", &[], Some("9"), None),
+            rule("x", &[], None, Some("24:01")),
+            rule("x", &["someday"], None, None),
+            Rule {
+                timezone: Some("Mars/Olympus".to_string()),
+                ..rule("x", &[], None, None)
+            },
+        ];
+        for rule in bad {
+            assert!(
+                matches!(rule.matches(at(19, 12, 0)), Err(SwitchError::InvalidRule(..))),
+                "{:?}",
+                rule
+            );
+        }
+    }
+
+    #[test]
+    fn higher_priority_wins_then_config_order() {
+        let rules = vec![
+            rule("first", &[], None, None),
+            Rule {
+                priority: 5,
+                ..rule("urgent", &["mon"], Some("09:00"), Some("10:00"))
+            },
+            rule("second", &[], None, None),
+            rule("tuesday", &["tue"], None, None),
+        ];
+
+        let order = |at: i64| -> Vec<String> {
+            matching(&rules, at)
+                .unwrap()
+                .into_iter()
+                .map(|(_, r)| r.account.clone())
+                .collect()
+        };
+        assert_eq!(order(at(19, 9, 30)), ["urgent", "first", "second"]);
+        assert_eq!(order(at(19, 11, 0)), ["first", "second"]);
+        assert_eq!(order(at(20, 9, 30)), ["first", "second", "tuesday"]);
+    }
+}
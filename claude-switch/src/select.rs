@@ -0,0 +1,145 @@
+use crate::config::{RotationConfig, RotationStrategy};
+use crate::credentials::TokenStatus;
+use crate::metadata::AccountsMetadata;
+use crate::paths;
+
+/// Check whether an account can be switched to right now: its token has
+/// not expired, it isn't cooling down after a rate limit, and its saved
+/// credentials file is present
+pub fn is_available(meta: &AccountsMetadata, name: &str) -> bool {
+    let Some(info) = meta.get_account(name) else {
+        return false;
+    };
+
+    TokenStatus::from_expires_at(info.token_expires_at) != TokenStatus::Expired
+        && info.cooldown_remaining().is_none()
+        && paths::account_credentials_path(name).is_ok_and(|p| p.exists())
+}
+
+/// Pick the best account to switch to among `candidates`.
+///
+/// Unavailable accounts are skipped. The active account wins if it is an
+/// available candidate (no switch needed); otherwise tokens that are not
+/// about to expire are preferred, then the least recently used account.
+pub fn best_account<'a>(meta: &'a AccountsMetadata, candidates: &[&'a str]) -> Option<&'a str> {
+    let current = meta.current_account.as_deref();
+
+    let available: Vec<&str> = candidates
+        .iter()
+        .copied()
+        .filter(|name| is_available(meta, name))
+        .collect();
+
+    if let Some(current) = current {
+        if available.contains(&current) {
+            return Some(current);
+        }
+    }
+
+    available.into_iter().min_by_key(|name| {
+        let info = meta.get_account(name);
+        let expiring = info.is_some_and(|i| {
+            matches!(
+                TokenStatus::from_expires_at(i.token_expires_at),
+                TokenStatus::Warning { .. }
+            )
+        });
+        (expiring, info.map_or(0, |i| i.last_used_at), *name)
+    })
+}
+
+/// Accounts in rotation order: the configured order first, then the rest
+/// alphabetically
+pub fn rotation_order<'a>(meta: &'a AccountsMetadata, rotation: &'a RotationConfig) -> Vec<&'a str> {
+    let mut order: Vec<&str> = Vec::new();
+    for name in &rotation.order {
+        if meta.account_exists(name) && !order.contains(&name.as_str()) {
+            order.push(name);
+        }
+    }
+
+    let mut rest: Vec<&str> = meta
+        .accounts
+        .keys()
+        .map(|s| s.as_str())
+        .filter(|name| !order.contains(name))
+        .collect();
+    rest.sort();
+
+    order.extend(rest);
+    order
+}
+
+/// Pick the account `next` should rotate to, skipping the active account
+/// and any that aren't available
+pub fn next_account(
+    meta: &AccountsMetadata,
+    rotation: &RotationConfig,
+    strategy: RotationStrategy,
+) -> Option<String> {
+    let current = meta.current_account.as_deref();
+    let candidates = |names: Vec<&str>| -> Vec<String> {
+        names
+            .into_iter()
+            .filter(|name| Some(*name) != current && is_available(meta, name))
+            .map(String::from)
+            .collect()
+    };
+
+    match strategy {
+        RotationStrategy::Order => {
+            let order = rotation_order(meta, rotation);
+            candidates(cycle_from(&order, current)).into_iter().next()
+        }
+        RotationStrategy::Lru => candidates(meta.accounts.keys().map(|s| s.as_str()).collect())
+            .into_iter()
+            .min_by_key(|name| (last_used(meta, name), name.clone())),
+        RotationStrategy::Priority => {
+            candidates(meta.accounts.keys().map(|s| s.as_str()).collect())
+                .into_iter()
+                .min_by_key(|name| {
+                    let priority = rotation.priority.get(name).copied().unwrap_or(0);
+                    (-priority, last_used(meta, name), name.clone())
+                })
+        }
+    }
+}
+
+/// Pick the account `prev` should go back to.
+///
+/// With the `order` strategy this walks the rotation order backwards;
+/// otherwise it returns to the previously active account.
+pub fn prev_account(
+    meta: &AccountsMetadata,
+    rotation: &RotationConfig,
+    strategy: RotationStrategy,
+) -> Option<String> {
+    let current = meta.current_account.as_deref();
+
+    match strategy {
+        RotationStrategy::Order => {
+            let mut order = rotation_order(meta, rotation);
+            order.reverse();
+            cycle_from(&order, current)
+                .into_iter()
+                .find(|name| Some(*name) != current && is_available(meta, name))
+                .map(String::from)
+        }
+        RotationStrategy::Lru | RotationStrategy::Priority => meta
+            .previous_account
+            .clone()
+            .filter(|name| Some(name.as_str()) != current && is_available(meta, name)),
+    }
+}
+
+/// The names following `start` in `order`, wrapping around
+fn cycle_from<'a>(order: &[&'a str], start: Option<&str>) -> Vec<&'a str> {
+    let pos = start
+        .and_then(|s| order.iter().position(|name| *name == s))
+        .map_or(0, |i| i + 1);
+    order[pos..].iter().chain(order[..pos].iter()).copied().collect()
+}
+
+fn last_used(meta: &AccountsMetadata, name: &str) -> i64 {
+    meta.get_account(name).map_or(0, |info| info.last_used_at)
+}
@@ -0,0 +1,352 @@
+//! Optional encryption of saved credentials.
+//!
+//! When the store is encrypted, `accounts/.store.json` holds the KDF salt
+//! and parameters plus a verifier, and every saved credentials file (and
+//! the backup) holds an XChaCha20-Poly1305 envelope instead of plain JSON.
+//! The key is derived from a passphrase with Argon2id. The active
+//! `~/.claude/.credentials.json` always stays plaintext, since Claude Code
+//! reads it directly.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, AeadCore, OsRng};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::{KeyInit, XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::Mutex;
+use zeroize::Zeroizing;
+use crate::agent;
+use crate::error::{Result, SwitchError};
+use crate::paths;
+
+/// Environment variable that supplies the passphrase non-interactively
+pub const PASSPHRASE_ENV: &str = "CLAUDE_SWITCH_PASSPHRASE";
+
+/// Cipher name recorded in envelopes and the store header
+const CIPHER: &str = "xchacha20poly1305";
+
+/// Plaintext of the verifier, used to tell a wrong passphrase apart from
+/// a corrupt file
+const VERIFIER: &[u8] = b"claude-switch store key";
+
+/// A derived store key, wiped from memory when dropped
+pub type StoreKey = Zeroizing<[u8; 32]>;
+
+/// Key derived during this process, so one command prompts at most once.
+/// Wiped by [`forget_key`].
+static CACHED_KEY: Mutex<Option<StoreKey>> = Mutex::new(None);
+
+/// `accounts/.store.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoreHeader {
+    version: u32,
+    kdf: String,
+    salt: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    verifier: Envelope,
+}
+
+/// An encrypted blob as stored on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Envelope {
+    encrypted: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Check whether the store has been encrypted
+pub fn is_encrypted() -> Result<bool> {
+    Ok(paths::store_header_path()?.exists())
+}
+
+/// Check whether file contents are an encrypted envelope
+pub fn is_envelope(contents: &str) -> bool {
+    serde_json::from_str::<Envelope>(contents).is_ok()
+}
+
+/// The store key, asking for the passphrase if it isn't known yet
+pub fn key() -> Result<StoreKey> {
+    if let Some(key) = cached_key() {
+        return Ok(key);
+    }
+    let passphrase = read_passphrase("Store passphrase: ")?;
+    let key = unlock(&passphrase)?;
+    // Save the next command the prompt if an agent is running
+    if agent::socket_from_env().is_some() {
+        let _ = agent::set_key(&key);
+    }
+    Ok(key)
+}
+
+/// The store key if it is available without prompting: derived earlier
+/// in this process, held by an unlocked agent, or supplied through the
+/// environment
+pub fn cached_key() -> Option<StoreKey> {
+    if let Some(key) = CACHED_KEY.lock().ok()?.as_ref() {
+        return Some(key.clone());
+    }
+    // The agent may hold the key of an earlier store with another
+    // passphrase, so check it before trusting it
+    if let Some(key) = agent::get_key().filter(verify) {
+        remember(&key);
+        return Some(key);
+    }
+    let passphrase = Zeroizing::new(std::env::var(PASSPHRASE_ENV).ok()?);
+    unlock(&passphrase).ok()
+}
+
+/// Derive the key from a passphrase and check it against the verifier
+pub fn unlock(passphrase: &str) -> Result<StoreKey> {
+    unlock_at(&paths::store_header_path()?, passphrase)
+}
+
+fn unlock_at(header_path: &Path, passphrase: &str) -> Result<StoreKey> {
+    let header = read_header(header_path)?;
+    let key = derive_key(passphrase, &header)?;
+    open(&key, &header.verifier).map_err(|_| SwitchError::WrongPassphrase)?;
+    remember(&key);
+    Ok(key)
+}
+
+/// Check that a key opens this store
+pub fn verify(key: &StoreKey) -> bool {
+    paths::store_header_path()
+        .and_then(|path| read_header(&path))
+        .is_ok_and(|header| open(key, &header.verifier).is_ok())
+}
+
+/// Start encrypting the store: write a header for a new passphrase and
+/// return its key. Files are converted by the caller.
+pub fn create(passphrase: &str) -> Result<StoreKey> {
+    paths::ensure_accounts_dir()?;
+    create_at(&paths::store_header_path()?, passphrase)
+}
+
+fn create_at(header_path: &Path, passphrase: &str) -> Result<StoreKey> {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+
+    let params = Params::default();
+    let mut header = StoreHeader {
+        version: 1,
+        kdf: "argon2id".to_string(),
+        salt: hex_encode(&salt),
+        memory_kib: params.m_cost(),
+        iterations: params.t_cost(),
+        parallelism: params.p_cost(),
+        verifier: Envelope {
+            encrypted: CIPHER.to_string(),
+            nonce: String::new(),
+            ciphertext: String::new(),
+        },
+    };
+
+    let key = derive_key(passphrase, &header)?;
+    header.verifier = seal(&key, VERIFIER)?;
+
+    write_private(header_path, &serde_json::to_string_pretty(&header)?)?;
+    remember(&key);
+    Ok(key)
+}
+
+/// Finish decrypting the store by removing its header
+pub fn remove_header() -> Result<()> {
+    std::fs::remove_file(paths::store_header_path()?)?;
+    forget_key();
+    Ok(())
+}
+
+/// Wipe the key cached in this process
+pub fn forget_key() {
+    if let Ok(mut cached) = CACHED_KEY.lock() {
+        drop(cached.take());
+    }
+}
+
+/// Encrypt `plaintext` into envelope JSON
+pub fn encrypt(key: &StoreKey, plaintext: &[u8]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&seal(key, plaintext)?)?)
+}
+
+/// Decrypt envelope JSON read from `path`
+pub fn decrypt(key: &StoreKey, contents: &str, path: &Path) -> Result<Zeroizing<Vec<u8>>> {
+    let corrupt = || SwitchError::DecryptFailed(path.display().to_string());
+    let envelope: Envelope = serde_json::from_str(contents).map_err(|_| corrupt())?;
+    open(key, &envelope).map_err(|_| corrupt())
+}
+
+/// Ask for a passphrase: from the environment, else on the terminal
+pub fn read_passphrase(prompt: &str) -> Result<Zeroizing<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(Zeroizing::new(passphrase));
+    }
+    if !std::io::stdin().is_terminal() {
+        return Err(SwitchError::StoreLocked);
+    }
+    Ok(Zeroizing::new(rpassword::prompt_password(prompt)?))
+}
+
+/// Write a file readable only by the owner, replacing it atomically.
+///
+/// The temporary file is created with mode 0600, so the contents are
+/// never readable by others, not even briefly.
+pub fn write_private(path: &Path, contents: &str) -> Result<()> {
+    use std::io::Write;
+
+    let tmp = path.with_extension("tmp");
+    // Left behind by an interrupted write; it may have other permissions
+    match std::fs::remove_file(&tmp) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&tmp)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn remember(key: &StoreKey) {
+    if let Ok(mut cached) = CACHED_KEY.lock() {
+        *cached = Some(key.clone());
+    }
+}
+
+fn read_header(path: &Path) -> Result<StoreHeader> {
+    let contents = std::fs::read_to_string(path)?;
+    serde_json::from_str(&contents)
+        .map_err(|_| SwitchError::DecryptFailed(path.display().to_string()))
+}
+
+fn derive_key(passphrase: &str, header: &StoreHeader) -> Result<StoreKey> {
+    let invalid = || SwitchError::DecryptFailed("store header".to_string());
+    let salt = hex_decode(&header.salt).ok_or_else(invalid)?;
+    let params = Params::new(header.memory_kib, header.iterations, header.parallelism, Some(32))
+        .map_err(|_| invalid())?;
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), &salt, key.as_mut())
+        .map_err(|_| invalid())?;
+    Ok(key)
+}
+
+fn seal(key: &StoreKey, plaintext: &[u8]) -> Result<Envelope> {
+    let cipher = XChaCha20Poly1305::new(key.as_ref().into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| SwitchError::DecryptFailed("encryption failed".to_string()))?;
+
+    Ok(Envelope {
+        encrypted: CIPHER.to_string(),
+        nonce: hex_encode(&nonce),
+        ciphertext: hex_encode(&ciphertext),
+    })
+}
+
+fn open(key: &StoreKey, envelope: &Envelope) -> std::result::Result<Zeroizing<Vec<u8>>, ()> {
+    if envelope.encrypted != CIPHER {
+        return Err(());
+    }
+    let nonce = hex_decode(&envelope.nonce).filter(|n| n.len() == 24).ok_or(())?;
+    let ciphertext = hex_decode(&envelope.ciphertext).ok_or(())?;
+
+    let cipher = XChaCha20Poly1305::new(key.as_ref().into());
+    cipher
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+        .map(Zeroizing::new)
+        .map_err(|_| ())
+}
+
+pub fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn hex_decode(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let header = dir.path().join(".store.json");
+        (dir, header)
+    }
+
+    #[test]
+    fn round_trip() {
+        let (_dir, header) = store();
+        let key = create_at(&header, "correct horse").unwrap();
+
+        let sealed = encrypt(&key, b"{\"token\":\"secret\"}").unwrap();
+        assert!(is_envelope(&sealed));
+        assert!(!sealed.contains("secret"));
+
+        let unlocked = unlock_at(&header, "correct horse").unwrap();
+        let plaintext = decrypt(&unlocked, &sealed, Path::new("account.json")).unwrap();
+        assert_eq!(plaintext.as_slice(), b"{\"token\":\"secret\"}");
+    }
+
+    #[test]
+    fn wrong_passphrase_is_rejected() {
+        let (_dir, header) = store();
+        create_at(&header, "correct horse").unwrap();
+        assert!(matches!(unlock_at(&header, "battery staple"), Err(SwitchError::WrongPassphrase)));
+    }
+
+    #[test]
+    fn tampering_is_detected() {
+        let (_dir, header) = store();
+        let key = create_at(&header, "correct horse").unwrap();
+        let sealed = encrypt(&key, b"credentials").unwrap();
+
+        let mut envelope: Envelope = serde_json::from_str(&sealed).unwrap();
+        let flipped = if envelope.ciphertext.starts_with('0') { "1" } else { "0" };
+        envelope.ciphertext.replace_range(..1, flipped);
+        let tampered = serde_json::to_string(&envelope).unwrap();
+
+        assert!(matches!(
+            decrypt(&key, &tampered, Path::new("account.json")),
+            Err(SwitchError::DecryptFailed(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn private_files_are_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let (_dir, header) = store();
+        // A stale temporary file with loose permissions is replaced
+        std::fs::write(header.with_extension("tmp"), "old").unwrap();
+        std::fs::set_permissions(header.with_extension("tmp"), std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private(&header, "{}").unwrap();
+        let mode = std::fs::metadata(&header).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(std::fs::read_to_string(&header).unwrap(), "{}");
+    }
+}
@@ -0,0 +1,119 @@
+use colored::Colorize;
+use unicode_width::UnicodeWidthChar;
+use crate::ui;
+
+/// Simple column-aligned table that measures cells by their visible width,
+/// so ANSI colour codes and wide characters don't break alignment
+#[derive(Debug, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Create a table with the given column headers
+    pub fn new<S: Into<String>>(headers: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            headers: headers.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a row; missing trailing cells render empty
+    pub fn add_row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    /// Visible width of each column
+    fn column_widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| display_width(h)).collect();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate() {
+                if i < widths.len() {
+                    widths[i] = widths[i].max(display_width(cell));
+                }
+            }
+        }
+        widths
+    }
+
+    /// Total visible width of a rendered line
+    pub fn width(&self) -> usize {
+        let widths = self.column_widths();
+        widths.iter().sum::<usize>() + widths.len().saturating_sub(1) * 2
+    }
+
+    /// Render the header line (dimmed)
+    pub fn header_line(&self) -> String {
+        let widths = self.column_widths();
+        let cells: Vec<String> = self.headers.iter().map(|h| h.dimmed().to_string()).collect();
+        join_padded(&cells, &widths)
+    }
+
+    /// Render every data row
+    pub fn row_lines(&self) -> Vec<String> {
+        let widths = self.column_widths();
+        self.rows.iter().map(|row| join_padded(row, &widths)).collect()
+    }
+}
+
+fn join_padded(cells: &[String], widths: &[usize]) -> String {
+    let mut line = String::new();
+    for (i, width) in widths.iter().enumerate() {
+        let cell = cells.get(i).map(String::as_str).unwrap_or("");
+        if i > 0 {
+            line.push_str("  ");
+        }
+        line.push_str(cell);
+        line.push_str(&" ".repeat(width.saturating_sub(display_width(cell))));
+    }
+    line.trim_end().to_string()
+}
+
+/// Width of a string as shown in a terminal, ignoring ANSI escape sequences
+pub fn display_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // CSI sequence: ESC [ params... final byte in @..~
+            if chars.peek() == Some(&'[') {
+                chars.next();
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        width += c.width().unwrap_or(0);
+    }
+
+    width
+}
+
+/// Cut a plain string down to `max` visible columns, ending with an
+/// ellipsis from the glyph set
+pub fn truncate(s: &str, max: usize) -> String {
+    if display_width(s) <= max {
+        return s.to_string();
+    }
+
+    let ellipsis = ui::glyphs().ellipsis;
+    let ellipsis_width = display_width(ellipsis);
+
+    let mut out = String::new();
+    let mut width = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if width + w + ellipsis_width > max {
+            break;
+        }
+        out.push(c);
+        width += w;
+    }
+    out.push_str(ellipsis);
+    out
+}
@@ -0,0 +1,226 @@
+use chrono::TimeZone;
+use crate::error::{Result, SwitchError};
+
+const MINUTE: i64 = 60 * 1000;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+
+/// Format a millisecond timestamp in local time
+pub fn local_time(ms: i64) -> String {
+    chrono::Local
+        .timestamp_millis_opt(ms)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Local wall-clock time of a millisecond timestamp, e.g. `15:20`
+pub fn clock_time(ms: i64) -> String {
+    chrono::Local
+        .timestamp_millis_opt(ms)
+        .single()
+        .map(|t| t.format("%H:%M").to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Describe a millisecond timestamp relative to now, e.g. `3h ago`,
+/// `yesterday` or `in 4d`
+pub fn relative_time(ms: i64) -> String {
+    relative_to(ms, chrono::Utc::now().timestamp_millis())
+}
+
+/// Relative time with the absolute local time appended
+pub fn relative_and_local(ms: i64) -> String {
+    format!("{} ({})", relative_time(ms), local_time(ms))
+}
+
+fn relative_to(ms: i64, now: i64) -> String {
+    let delta = ms - now;
+    let future = delta > 0;
+    let abs = delta.abs();
+
+    let amount = if abs < MINUTE {
+        return "just now".to_string();
+    } else if abs < HOUR {
+        format!("{}m", abs / MINUTE)
+    } else if abs < DAY {
+        format!("{}h", abs / HOUR)
+    } else if abs < 2 * DAY {
+        return if future { "tomorrow" } else { "yesterday" }.to_string();
+    } else if abs < 60 * DAY {
+        format!("{}d", abs / DAY)
+    } else if abs < 365 * DAY {
+        format!("{}mo", abs / (30 * DAY))
+    } else {
+        format!("{}y", abs / (365 * DAY))
+    };
+
+    if future {
+        format!("in {}", amount)
+    } else {
+        format!("{} ago", amount)
+    }
+}
+
+/// Compact countdown for a positive duration, e.g. `3h 20m` or `2d 4h`
+pub fn countdown(ms: i64) -> String {
+    let ms = ms.max(0);
+    let (days, hours, minutes) = (ms / DAY, (ms % DAY) / HOUR, (ms % HOUR) / MINUTE);
+
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        // Round up so the last minute doesn't show as `0m`
+        format!("{}m", minutes + i64::from(ms % MINUTE > 0))
+    }
+}
+
+/// Parse a duration such as `5h`, `90m`, `1h30m` or `2d` into milliseconds
+pub fn parse_duration(input: &str) -> Result<i64> {
+    let invalid = || SwitchError::InvalidDuration(input.to_string());
+    let mut total = 0i64;
+    let mut digits = String::new();
+
+    for c in input.trim().chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            'd' => DAY,
+            'h' => HOUR,
+            'm' => MINUTE,
+            's' => 1000,
+            _ => return Err(invalid()),
+        };
+        let value: i64 = digits.parse().map_err(|_| invalid())?;
+        total = value
+            .checked_mul(unit)
+            .and_then(|v| total.checked_add(v))
+            .ok_or_else(invalid)?;
+        digits.clear();
+    }
+
+    if !digits.is_empty() || total <= 0 {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+/// Parse a point in time into a millisecond timestamp.
+///
+/// Accepts RFC 3339, `YYYY-MM-DD HH:MM` in local time, or a bare `HH:MM`
+/// meaning the next time the local clock shows it.
+pub fn parse_time(input: &str) -> Result<i64> {
+    let input = input.trim();
+
+    if let Ok(t) = chrono::DateTime::parse_from_rfc3339(input) {
+        return Ok(t.timestamp_millis());
+    }
+
+    if let Ok(t) = chrono::NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M") {
+        if let Some(local) = chrono::Local.from_local_datetime(&t).earliest() {
+            return Ok(local.timestamp_millis());
+        }
+    }
+
+    if let Ok(time) = chrono::NaiveTime::parse_from_str(input, "%H:%M") {
+        let now = chrono::Local::now();
+        let today = now.date_naive().and_time(time);
+        let mut at = chrono::Local.from_local_datetime(&today).earliest();
+        if at.is_some_and(|t| t <= now) {
+            let tomorrow = today + chrono::Duration::days(1);
+            at = chrono::Local.from_local_datetime(&tomorrow).earliest();
+        }
+        if let Some(at) = at {
+            return Ok(at.timestamp_millis());
+        }
+    }
+
+    Err(SwitchError::InvalidTime(input.to_string()))
+}
+
+/// Parse the start of a reporting period: a duration back from now
+/// (`7d`, `12h`), a date (`YYYY-MM-DD`, local midnight) or a time as
+/// accepted by [`parse_time`]
+pub fn parse_since(input: &str) -> Result<i64> {
+    if let Ok(duration) = parse_duration(input) {
+        return Ok(chrono::Utc::now().timestamp_millis() - duration);
+    }
+
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d") {
+        let midnight = date.and_time(chrono::NaiveTime::MIN);
+        if let Some(local) = chrono::Local.from_local_datetime(&midnight).earliest() {
+            return Ok(local.timestamp_millis());
+        }
+    }
+
+    parse_time(input)
+}
+
+/// Local calendar date of a millisecond timestamp, e.g. `2026-10-19`
+pub fn local_date(ms: i64) -> String {
+    chrono::Local
+        .timestamp_millis_opt(ms)
+        .single()
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("5h").unwrap(), 5 * HOUR);
+        assert_eq!(parse_duration("90m").unwrap(), 90 * MINUTE);
+        assert_eq!(parse_duration("1h30m").unwrap(), HOUR + 30 * MINUTE);
+        assert_eq!(parse_duration("2d").unwrap(), 2 * DAY);
+        assert_eq!(parse_duration("45s").unwrap(), 45_000);
+        assert_eq!(parse_duration(" 10m ").unwrap(), 10 * MINUTE);
+    }
+
+    #[test]
+    fn rejects_malformed_durations() {
+        for input in ["", "5", "h", "5x", "1h30", "0m", "-5m", "1.5h", "5 h", "99999999999999999d"] {
+            assert!(
+                matches!(parse_duration(input), Err(SwitchError::InvalidDuration(_))),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn describes_relative_times() {
+        let now = 1_000 * DAY;
+        assert_eq!(relative_to(now - 30_000, now), "just now");
+        assert_eq!(relative_to(now - 5 * MINUTE, now), "5m ago");
+        assert_eq!(relative_to(now + 3 * HOUR, now), "in 3h");
+        assert_eq!(relative_to(now - 30 * HOUR, now), "yesterday");
+        assert_eq!(relative_to(now + 30 * HOUR, now), "tomorrow");
+        assert_eq!(relative_to(now - 10 * DAY, now), "10d ago");
+        assert_eq!(relative_to(now - 90 * DAY, now), "3mo ago");
+        assert_eq!(relative_to(now + 400 * DAY, now), "in 1y");
+    }
+
+    #[test]
+    fn formats_countdowns() {
+        assert_eq!(countdown(2 * DAY + 4 * HOUR), "2d 4h");
+        assert_eq!(countdown(3 * HOUR + 20 * MINUTE), "3h 20m");
+        assert_eq!(countdown(30_000), "1m");
+        assert_eq!(countdown(-5), "0m");
+    }
+
+    #[test]
+    fn parses_times() {
+        assert_eq!(parse_time("2026-10-19T10:00:00Z").unwrap(), 1_792_404_000_000);
+        assert!(parse_time("12:30").unwrap() > chrono::Utc::now().timestamp_millis());
+        for input in ["25:00", "tomorrow", "2026-13-01 10:00"] {
+            assert!(matches!(parse_time(input), Err(SwitchError::InvalidTime(_))), "{:?}", input);
+        }
+    }
+}
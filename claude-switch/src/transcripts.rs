@@ -0,0 +1,359 @@
+//! Reading Claude Code session transcripts (`~/.claude/projects/**/*.jsonl`).
+
+use chrono::{NaiveTime, TimeZone};
+use serde::Deserialize;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use crate::error::Result;
+
+/// Assumed usage window when a limit message carries no reset time
+const DEFAULT_LIMIT_WINDOW_MS: i64 = 5 * 60 * 60 * 1000;
+
+/// Model name Claude Code records on messages it makes up itself, such as
+/// API errors
+const SYNTHETIC_MODEL: &str = "<synthetic>";
+
+/// Exact start of the machine-readable usage-limit message
+const LIMIT_PREFIX: &str = "Claude AI usage limit reached|";
+
+/// One transcript line; only the fields claude-switch uses
+#[derive(Debug, Clone, Deserialize)]
+pub struct Entry {
+    #[serde(rename = "type", default)]
+    pub kind: String,
+
+    #[serde(default)]
+    pub timestamp: Option<String>,
+
+    #[serde(rename = "sessionId", default)]
+    pub session_id: Option<String>,
+
+    #[serde(rename = "requestId", default)]
+    pub request_id: Option<String>,
+
+    #[serde(rename = "isApiErrorMessage", default)]
+    pub is_api_error: bool,
+
+    #[serde(default)]
+    pub message: Option<Message>,
+}
+
+/// The API message inside a transcript entry
+#[derive(Debug, Clone, Deserialize)]
+pub struct Message {
+    #[serde(default)]
+    pub id: Option<String>,
+
+    #[serde(default)]
+    pub model: Option<String>,
+
+    #[serde(default)]
+    pub usage: Option<Usage>,
+
+    #[serde(default)]
+    pub content: Option<serde_json::Value>,
+}
+
+/// Token counts reported for one API response
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct Usage {
+    #[serde(default)]
+    pub input_tokens: u64,
+
+    #[serde(default)]
+    pub output_tokens: u64,
+
+    #[serde(default)]
+    pub cache_creation_input_tokens: u64,
+
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+}
+
+impl Entry {
+    /// Entry timestamp in milliseconds
+    pub fn timestamp_ms(&self) -> Option<i64> {
+        let ts = self.timestamp.as_deref()?;
+        chrono::DateTime::parse_from_rfc3339(ts)
+            .ok()
+            .map(|t| t.timestamp_millis())
+    }
+
+    /// All text blocks of the message, joined
+    pub fn text(&self) -> String {
+        match self.message.as_ref().and_then(|m| m.content.as_ref()) {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(serde_json::Value::Array(blocks)) => blocks
+                .iter()
+                .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => String::new(),
+        }
+    }
+}
+
+/// A usage-limit message found in a transcript
+#[derive(Debug, Clone)]
+pub struct LimitEvent {
+    /// When the limit was hit (ms)
+    pub at: i64,
+
+    /// When the limit resets (ms)
+    pub resets_at: i64,
+
+    pub session_id: Option<String>,
+}
+
+/// Directory Claude Code keeps the transcripts of sessions started in
+/// `cwd`: the path with every non-alphanumeric character replaced by `-`
+pub fn project_dir(projects_dir: &Path, cwd: &Path) -> PathBuf {
+    let encoded: String = cwd
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    projects_dir.join(encoded)
+}
+
+/// All transcript files under a projects directory
+pub fn transcript_files(projects_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if projects_dir.is_dir() {
+        collect_jsonl(projects_dir, &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn collect_jsonl(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_jsonl(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "jsonl") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Read complete lines appended to `path` since byte `offset`.
+///
+/// Returns the lines and the offset to resume from. A trailing partial
+/// line is left for the next read; a file that shrank is read from the
+/// start again.
+pub fn read_new_lines(path: &Path, offset: u64) -> Result<(Vec<String>, u64)> {
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = if len < offset { 0 } else { offset };
+
+    file.seek(SeekFrom::Start(start))?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+
+    let Some(last_newline) = buf.iter().rposition(|b| *b == b'\n') else {
+        return Ok((Vec::new(), start));
+    };
+
+    let lines = String::from_utf8_lossy(&buf[..last_newline])
+        .lines()
+        .map(String::from)
+        .collect();
+    Ok((lines, start + last_newline as u64 + 1))
+}
+
+/// Parse a transcript line, returning `None` for anything unreadable
+pub fn parse_line(line: &str) -> Option<Entry> {
+    serde_json::from_str(line).ok()
+}
+
+/// Detect a usage-limit message in an assistant entry.
+///
+/// Understands the `Claude AI usage limit reached|<epoch>` form as well as
+/// prose such as `5-hour limit reached ∙ resets 3pm (Europe/Berlin)`. Prose
+/// only counts on the synthetic error entries Claude Code writes itself,
+/// so model replies that merely mention a limit are ignored.
+pub fn limit_event(entry: &Entry) -> Option<LimitEvent> {
+    if entry.kind != "assistant" {
+        return None;
+    }
+
+    let text = entry.text();
+    if !text.starts_with(LIMIT_PREFIX) {
+        let synthetic = entry.is_api_error
+            || entry
+                .message
+                .as_ref()
+                .is_some_and(|m| m.model.as_deref() == Some(SYNTHETIC_MODEL));
+        let lower = text.to_ascii_lowercase();
+        if !synthetic || !(lower.contains("limit reached") || lower.contains("hit your limit")) {
+            return None;
+        }
+    }
+
+    let at = entry.timestamp_ms()?;
+    let resets_at = epoch_reset(&text)
+        .or_else(|| clock_reset(&text, at))
+        .unwrap_or(at + DEFAULT_LIMIT_WINDOW_MS);
+
+    Some(LimitEvent {
+        at,
+        resets_at,
+        session_id: entry.session_id.clone(),
+    })
+}
+
+/// `...limit reached|1760000000` (seconds, or milliseconds)
+fn epoch_reset(text: &str) -> Option<i64> {
+    let (_, rest) = text.split_once("limit reached|")?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let value: i64 = digits.parse().ok()?;
+    Some(if value < 10_000_000_000 { value * 1000 } else { value })
+}
+
+/// `resets 3pm`, `resets at 3:30 pm (Europe/Berlin)`, `reset at 15:00`
+fn clock_reset(text: &str, at: i64) -> Option<i64> {
+    let lower = text.to_ascii_lowercase();
+    let start = ["resets at ", "resets ", "reset at "]
+        .iter()
+        .find_map(|marker| lower.find(marker).map(|i| i + marker.len()))?;
+
+    let clause = &lower[start..];
+    let clock: String = clause
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == ':')
+        .collect();
+    let after = clause[clock.len()..].trim_start();
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((h, m)) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        None => (clock.parse::<u32>().ok()?, 0),
+    };
+    let hour = if after.starts_with("pm") && hour < 12 {
+        hour + 12
+    } else if after.starts_with("am") && hour == 12 {
+        0
+    } else {
+        hour
+    };
+    let time = NaiveTime::from_hms_opt(hour, minute, 0)?;
+
+    // Optional IANA timezone in parentheses, case preserved
+    let tz = text[start..]
+        .split_once('(')
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .and_then(|(name, _)| name.trim().parse::<chrono_tz::Tz>().ok());
+
+    match tz {
+        Some(tz) => next_occurrence(&tz, time, at),
+        None => next_occurrence(&chrono::Local, time, at),
+    }
+}
+
+/// First time after `at` (ms) that the clock in `tz` shows `time`
+fn next_occurrence<Tz: TimeZone>(tz: &Tz, time: NaiveTime, at: i64) -> Option<i64> {
+    let base = tz.timestamp_millis_opt(at).single()?;
+    let today = base.date_naive().and_time(time);
+
+    let candidate = tz.from_local_datetime(&today).earliest()?;
+    if candidate.timestamp_millis() > at {
+        return Some(candidate.timestamp_millis());
+    }
+    let tomorrow = today + chrono::Duration::days(1);
+    Some(tz.from_local_datetime(&tomorrow).earliest()?.timestamp_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AT: &str = "2026-10-19T10:00:00Z";
+
+    fn assistant(model: &str, text: &str, api_error: bool) -> Entry {
+        let line = serde_json::json!({
+            "type": "assistant",
+            "timestamp": AT,
+            "sessionId": "s1",
+            "isApiErrorMessage": api_error,
+            "message": {
+                "model": model,
+                "content": [{ "type": "text", "text": text }],
+            },
+        });
+        parse_line(&line.to_string()).unwrap()
+    }
+
+    #[test]
+    fn detects_epoch_prefix() {
+        let entry = assistant("claude-sonnet-4", "Claude AI usage limit reached|1760900000", false);
+        let event = limit_event(&entry).unwrap();
+        assert_eq!(event.resets_at, 1_760_900_000_000);
+        assert_eq!(event.session_id.as_deref(), Some("s1"));
+    }
+
+    const HOUR: i64 = 60 * 60 * 1000;
+
+    #[test]
+    fn detects_synthetic_prose() {
+        let entry = assistant("<synthetic>", "5-hour limit reached ∙ resets 3:30pm (Europe/Berlin)", false);
+        let event = limit_event(&entry).unwrap();
+        let at = entry.timestamp_ms().unwrap();
+        assert_eq!(event.at, at);
+        // 15:30 CEST is 13:30Z
+        assert_eq!(event.resets_at, at + 3 * HOUR + HOUR / 2);
+    }
+
+    #[test]
+    fn clock_reset_forms() {
+        let at = chrono::DateTime::parse_from_rfc3339(AT).unwrap().timestamp_millis();
+        // Already past today, so tomorrow
+        assert_eq!(clock_reset("resets 9am (UTC)", at), Some(at + 23 * HOUR));
+        assert_eq!(clock_reset("resets 11:30am (Europe/Berlin)", at), Some(at + 23 * HOUR + HOUR / 2));
+        assert_eq!(clock_reset("resets at 12am (UTC)", at), Some(at + 14 * HOUR));
+        assert_eq!(clock_reset("reset at 12:15pm (UTC)", at), Some(at + 2 * HOUR + HOUR / 4));
+        assert_eq!(clock_reset("resets 18:45 (UTC)", at), Some(at + 8 * HOUR + 3 * HOUR / 4));
+        assert_eq!(clock_reset("limit reached, try later", at), None);
+    }
+
+    #[test]
+    fn unknown_timezone_uses_local_time() {
+        let at = chrono::DateTime::parse_from_rfc3339(AT).unwrap().timestamp_millis();
+        let three_pm = NaiveTime::from_hms_opt(15, 0, 0).unwrap();
+        assert_eq!(
+            clock_reset("resets 3pm (Mars/Olympus)", at),
+            next_occurrence(&chrono::Local, three_pm, at)
+        );
+    }
+
+    #[test]
+    fn detects_api_error_entry() {
+        let entry = assistant("claude-sonnet-4", "You've hit your limit", true);
+        assert!(limit_event(&entry).is_some());
+    }
+
+    #[test]
+    fn ignores_model_replies_mentioning_limits() {
+        let replies = [
+            "The usage limit reached message comes from the API.",
+            "If you hit your limit, claude-switch rotates accounts.",
+            "Look for 'Claude AI usage limit reached|1760900000' in transcripts.",
+        ];
+        for text in replies {
+            assert!(limit_event(&assistant("claude-sonnet-4", text, false)).is_none(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn ignores_other_synthetic_errors() {
+        let entry = assistant("<synthetic>", "API Error: 529 Overloaded", true);
+        assert!(limit_event(&entry).is_none());
+    }
+
+    #[test]
+    fn ignores_user_entries() {
+        let mut entry = assistant("<synthetic>", "Claude AI usage limit reached|1760900000", false);
+        entry.kind = "user".to_string();
+        assert!(limit_event(&entry).is_none());
+    }
+}
@@ -0,0 +1,175 @@
+//! Shared terminal rendering: colour policy, glyph sets and message layout.
+
+use clap::ValueEnum;
+use colored::{ColoredString, Colorize};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::table::Table;
+
+/// Width of labels printed by [`field`]
+const LABEL_WIDTH: usize = 17;
+
+/// When to emit ANSI colour codes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Colour when stdout is a terminal, honouring NO_COLOR / CLICOLOR_FORCE
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Which characters to use for marks and rules
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GlyphSet {
+    #[default]
+    Unicode,
+    Ascii,
+}
+
+/// The characters used for one glyph set
+#[derive(Debug)]
+pub struct Glyphs {
+    pub ok: &'static str,
+    pub warn: &'static str,
+    pub error: &'static str,
+    pub info: &'static str,
+    pub active: &'static str,
+    pub rule: &'static str,
+    pub ellipsis: &'static str,
+    pub range: &'static str,
+}
+
+const UNICODE: Glyphs = Glyphs {
+    ok: "✓",
+    warn: "⚠",
+    error: "✗",
+    info: "ℹ",
+    active: "●",
+    rule: "─",
+    ellipsis: "…",
+    range: "–",
+};
+
+const ASCII: Glyphs = Glyphs {
+    ok: "+",
+    warn: "!",
+    error: "x",
+    info: "i",
+    active: "*",
+    rule: "-",
+    ellipsis: "...",
+    range: "-",
+};
+
+static USE_ASCII: AtomicBool = AtomicBool::new(false);
+
+/// Apply the colour policy and glyph set for this process
+pub fn init(color: ColorChoice, glyphs: GlyphSet) {
+    colored::control::set_override(should_color(color));
+    USE_ASCII.store(glyphs == GlyphSet::Ascii, Ordering::Relaxed);
+}
+
+/// Resolve `auto` against the environment and whether stdout is a terminal
+fn should_color(choice: ColorChoice) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            let env_set = |key: &str| std::env::var_os(key).is_some_and(|v| !v.is_empty());
+            let env_is = |key: &str, value: &str| std::env::var(key).is_ok_and(|v| v == value);
+
+            if env_set("NO_COLOR") {
+                false
+            } else if env_set("CLICOLOR_FORCE") && !env_is("CLICOLOR_FORCE", "0") {
+                true
+            } else if env_is("CLICOLOR", "0") {
+                false
+            } else {
+                std::io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// The active glyph set
+pub fn glyphs() -> &'static Glyphs {
+    if USE_ASCII.load(Ordering::Relaxed) {
+        &ASCII
+    } else {
+        &UNICODE
+    }
+}
+
+pub fn ok_mark() -> ColoredString {
+    glyphs().ok.green()
+}
+
+pub fn warn_mark() -> ColoredString {
+    glyphs().warn.yellow()
+}
+
+pub fn error_mark() -> ColoredString {
+    glyphs().error.red()
+}
+
+pub fn info_mark() -> ColoredString {
+    glyphs().info.blue()
+}
+
+pub fn active_mark() -> ColoredString {
+    glyphs().active.green()
+}
+
+/// A horizontal rule of the given width
+pub fn rule(width: usize) -> String {
+    glyphs().rule.repeat(width)
+}
+
+/// `✓ message`
+pub fn success(msg: impl Display) {
+    println!("{} {}", ok_mark(), msg);
+}
+
+/// `⚠ message`
+pub fn warning(msg: impl Display) {
+    println!("{} {}", warn_mark(), msg);
+}
+
+/// `ℹ message`
+pub fn info(msg: impl Display) {
+    println!("{} {}", info_mark(), msg);
+}
+
+/// Dimmed hint line
+pub fn hint(msg: impl Display) {
+    println!("{}", msg.to_string().dimmed());
+}
+
+/// Bold title followed by a rule
+pub fn heading(title: &str, width: usize) {
+    println!("{}", title.bold());
+    println!("{}", rule(width));
+}
+
+/// Indented `Label:  value` line with aligned values
+pub fn field(label: &str, value: impl Display) {
+    let pad = LABEL_WIDTH.saturating_sub(label.chars().count());
+    println!("  {}{}{}", label.dimmed(), " ".repeat(pad), value);
+}
+
+/// Titled table framed by rules
+pub fn table(title: &str, table: &Table) {
+    let width = table.width() + 2;
+    heading(title, width);
+    println!("  {}", table.header_line());
+    println!("{}", rule(width));
+    for line in table.row_lines() {
+        println!("  {}", line);
+    }
+    println!("{}", rule(width));
+}
@@ -0,0 +1,179 @@
+//! Token usage per account, reconstructed offline from session transcripts
+//! and the switch history.
+
+use serde::Serialize;
+use std::collections::HashSet;
+use std::ops::AddAssign;
+use std::time::UNIX_EPOCH;
+use crate::error::Result;
+use crate::history;
+use crate::metadata::AccountsMetadata;
+use crate::paths;
+use crate::transcripts;
+
+/// Length of a Claude usage window
+pub const WINDOW_MS: i64 = 5 * 60 * 60 * 1000;
+
+const HOUR_MS: i64 = 60 * 60 * 1000;
+
+/// Token counts, split the way the API reports them
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct TokenCounts {
+    pub input: u64,
+    pub output: u64,
+    pub cache_creation: u64,
+    pub cache_read: u64,
+}
+
+impl TokenCounts {
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_creation + self.cache_read
+    }
+}
+
+impl AddAssign for TokenCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_creation += other.cache_creation;
+        self.cache_read += other.cache_read;
+    }
+}
+
+/// One assistant response, attributed to an account
+#[derive(Debug, Clone)]
+pub struct UsageRecord {
+    pub at: i64,
+    pub account: String,
+    pub model: String,
+    pub tokens: TokenCounts,
+}
+
+/// A 5-hour usage window of one account
+#[derive(Debug, Clone, Serialize)]
+pub struct Window {
+    pub account: String,
+    pub start: i64,
+    pub end: i64,
+    pub messages: u64,
+    pub tokens: TokenCounts,
+}
+
+/// Read every transcript and attribute each response with token usage to
+/// the account active at its timestamp.
+///
+/// Responses are de-duplicated by message and request id, since resumed
+/// sessions repeat earlier lines. Only responses at or after `since` (ms)
+/// are returned; the result is sorted by time.
+pub fn load_records(since: Option<i64>) -> Result<Vec<UsageRecord>> {
+    let meta = AccountsMetadata::load()?;
+    let switches = history::load()?;
+    let current = meta.current_account.as_deref();
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut records = Vec::new();
+
+    for file in transcripts::transcript_files(&paths::projects_dir()?)? {
+        if let Some(since) = since {
+            if modified_ms(&file).is_some_and(|m| m < since) {
+                continue;
+            }
+        }
+
+        let Ok((lines, _)) = transcripts::read_new_lines(&file, 0) else {
+            continue;
+        };
+
+        for line in lines {
+            if !line.contains("\"usage\"") {
+                continue;
+            }
+            let Some(entry) = transcripts::parse_line(&line) else {
+                continue;
+            };
+            if entry.kind != "assistant" {
+                continue;
+            }
+            let (Some(at), Some(message)) = (entry.timestamp_ms(), entry.message.as_ref()) else {
+                continue;
+            };
+            let Some(usage) = message.usage else {
+                continue;
+            };
+            if since.is_some_and(|since| at < since) {
+                continue;
+            }
+
+            let tokens = TokenCounts {
+                input: usage.input_tokens,
+                output: usage.output_tokens,
+                cache_creation: usage.cache_creation_input_tokens,
+                cache_read: usage.cache_read_input_tokens,
+            };
+            if tokens.total() == 0 {
+                continue;
+            }
+
+            if let (Some(id), Some(request)) = (&message.id, &entry.request_id) {
+                if !seen.insert(format!("{}:{}", id, request)) {
+                    continue;
+                }
+            }
+
+            let Some(account) = history::account_at(&switches, at, current) else {
+                continue;
+            };
+
+            records.push(UsageRecord {
+                at,
+                account: account.to_string(),
+                model: message.model.clone().unwrap_or_else(|| "unknown".to_string()),
+                tokens,
+            });
+        }
+    }
+
+    records.sort_by_key(|r| r.at);
+    Ok(records)
+}
+
+/// Group records into 5-hour windows per account.
+///
+/// A window opens at the hour of the first response outside the previous
+/// window and lasts [`WINDOW_MS`], matching how Claude's usage windows run.
+pub fn windows(records: &[UsageRecord]) -> Vec<Window> {
+    let mut windows: Vec<Window> = Vec::new();
+
+    for record in records {
+        let open = windows
+            .iter_mut()
+            .rev()
+            .find(|w| w.account == record.account)
+            .filter(|w| record.at < w.end);
+
+        match open {
+            Some(window) => {
+                window.messages += 1;
+                window.tokens += record.tokens;
+            }
+            None => {
+                let start = record.at - record.at.rem_euclid(HOUR_MS);
+                windows.push(Window {
+                    account: record.account.clone(),
+                    start,
+                    end: start + WINDOW_MS,
+                    messages: 1,
+                    tokens: record.tokens,
+                });
+            }
+        }
+    }
+
+    windows
+}
+
+fn modified_ms(path: &std::path::Path) -> Option<i64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let ms = modified.duration_since(UNIX_EPOCH).ok()?.as_millis();
+    i64::try_from(ms).ok()
+}